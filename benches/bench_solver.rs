@@ -34,5 +34,33 @@ fn bench_solver(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_solver, bench_moves);
+/// Searches which run long enough for the solver threads to share the termination flag and the best length.
+fn bench_solver_threads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Solver threads");
+    group.sample_size(10);
+    group.bench_function("max_length 18", |b| {
+        b.iter(|| {
+            solve(
+                "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF",
+                18,
+                10.0,
+            )
+            .unwrap()
+        })
+    });
+    // the superflip needs 20 moves
+    group.bench_function("superflip", |b| {
+        b.iter(|| {
+            solve(
+                "UBULURUFURURFRBRDRFUFLFRFDFDFDLDRDBDLULBLFLDLBUBRBLBDB",
+                20,
+                10.0,
+            )
+            .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_solver, bench_solver_threads, bench_moves);
 criterion_main!(benches);
@@ -6,15 +6,22 @@ use crossterm::{
 };
 use spinners::Spinner;
 use std::{
+    fs,
     io::{self, stdout},
     time::Instant,
 };
 
 use kociemba::{
+    analysis::analyze,
+    bld::{trace, BldOptions},
+    fmc::{find_multi_insertions, three_cycle_algorithms},
+    cross::solve_all_crosses,
     cubie::CubieCube,
+    reconstruction::reconstruct,
     facelet::FaceCube,
     scramble::{gen_scramble, scramble_to_str},
-    solver::solve as solver,
+    solver::{solve as solver, SearchLimits},
+    stats::{run_stats, StatsOptions},
 };
 use kociemba::{error::Error, facelet::Color, scramble::scramble_from_str};
 
@@ -54,6 +61,92 @@ enum Commands {
         preview: bool,
     },
 
+    #[command(about = "analyzes a scrambled cube")]
+    #[clap(group(
+    clap::ArgGroup::new("state")
+        .required(true)
+        .args(&["scramble", "facelet"]),
+    ))]
+    Analyze {
+        #[arg(short, long)]
+        scramble: Option<String>,
+
+        #[arg(short, long)]
+        facelet: Option<String>,
+
+        #[arg(short, long, default_value_t = 0)]
+        max: usize,
+
+        #[arg(short, long, default_value_t = 3.0)]
+        timeout: f32,
+    },
+
+    #[command(about = "solves seeded random cubes and reports statistics")]
+    Stats {
+        #[arg(short = 'n', long, default_value_t = 100)]
+        cubes: usize,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        #[arg(short, long, default_value_t = 20)]
+        max: usize,
+
+        #[arg(short, long, default_value_t = 3.0)]
+        timeout: f32,
+
+        #[arg(long)]
+        nodes: Option<u64>,
+
+        #[arg(long)]
+        single_threaded: bool,
+
+        #[arg(long)]
+        csv: Option<String>,
+
+        #[arg(long)]
+        json: Option<String>,
+    },
+
+    #[command(about = "finds the optimal crosses on all six faces")]
+    Cross {
+        #[arg(short, long)]
+        scramble: String,
+
+        #[arg(short, long)]
+        xcross: bool,
+    },
+
+    #[command(about = "traces the blindfolded memo with the Speffz letter scheme")]
+    Bld {
+        #[arg(short, long)]
+        scramble: String,
+    },
+
+    #[command(about = "finds 3-cycle insertions which complete a skeleton")]
+    Insert {
+        #[arg(short, long)]
+        scramble: String,
+
+        #[arg(short = 'k', long)]
+        skeleton: String,
+
+        #[arg(short, long, default_value_t = 1)]
+        insertions: usize,
+
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+    },
+
+    #[command(about = "reconstructs a CFOP solve step by step")]
+    Reconstruct {
+        #[arg(short, long)]
+        scramble: String,
+
+        #[arg(short = 'm', long)]
+        solution: String,
+    },
+
     #[command(about = "generates scramble")]
     Scramble {
         #[arg(short, long, default_value_t = 20)]
@@ -117,6 +210,95 @@ fn solve_facelet(facelet: &str, max: usize, timeout: f32, verbose: bool) -> Resu
     solve_state(facelet, max, timeout, verbose)
 }
 
+fn analyze_state(
+    scramble: &Option<String>,
+    facelet: &Option<String>,
+    max: usize,
+    timeout: f32,
+) -> Result<(), Error> {
+    let state = if let Some(scramble) = scramble {
+        CubieCube::from(&scramble_from_str(scramble)?)
+    } else if let Some(facelet) = facelet {
+        CubieCube::try_from(&FaceCube::try_from(facelet.as_str())?)?
+    } else {
+        return Ok(());
+    };
+    let limits = SearchLimits {
+        max_length: max,
+        timeout: Some(timeout),
+        ..Default::default()
+    };
+    let mut spinner = Spinner::new(spinners::Spinners::Dots, "Analyzing".to_owned());
+    let report = analyze(&state, &limits)?;
+    spinner.stop_with_newline();
+    println!("{report}");
+    Ok(())
+}
+
+fn stats(options: &StatsOptions, csv: &Option<String>, json: &Option<String>) -> Result<(), Error> {
+    let mut spinner = Spinner::new(
+        spinners::Spinners::Dots,
+        format!("Solving {} cubes", options.cubes),
+    );
+    let report = run_stats(options)?;
+    spinner.stop_with_newline();
+    println!("{report}");
+    if let Some(path) = csv {
+        fs::write(path, report.to_csv()?)?;
+    }
+    if let Some(path) = json {
+        fs::write(path, serde_json::to_string_pretty(&report).map_err(io::Error::from)?)?;
+    }
+    Ok(())
+}
+
+fn cross(scramble: &str, xcross: bool) -> Result<(), Error> {
+    let state = CubieCube::from(&scramble_from_str(scramble)?);
+    for crosses in solve_all_crosses(&state, xcross)? {
+        println!("{:?} ({} moves):", crosses.face, crosses.length);
+        for solution in &crosses.solutions {
+            println!("  {}", scramble_to_str(solution)?);
+        }
+    }
+    Ok(())
+}
+
+fn bld(scramble: &str) -> Result<(), Error> {
+    let state = CubieCube::from(&scramble_from_str(scramble)?);
+    let memo = trace(&state, &BldOptions::default())?;
+    println!("{memo}");
+    Ok(())
+}
+
+fn insert(scramble: &str, skeleton: &str, max_insertions: usize, count: usize) -> Result<(), Error> {
+    let state = CubieCube::from(&scramble_from_str(scramble)?);
+    let skeleton = scramble_from_str(skeleton)?;
+    let results = find_multi_insertions(&state, &skeleton, three_cycle_algorithms(), max_insertions);
+    if results.is_empty() {
+        println!("No insertion found");
+    }
+    for insertions in results.iter().take(count) {
+        let solution = &insertions.last().unwrap().solution;
+        println!("{} moves:", solution.len());
+        for insertion in insertions {
+            println!(
+                "  at {}: {} ({} cancelled)",
+                insertion.position,
+                scramble_to_str(&insertion.algorithm)?,
+                insertion.cancelled
+            );
+        }
+        println!("  {}", scramble_to_str(solution)?);
+    }
+    Ok(())
+}
+
+fn reconstruction(scramble: &str, solution: &str) -> Result<(), Error> {
+    let reconstruction = reconstruct(scramble, solution)?;
+    println!("{reconstruction}");
+    Ok(())
+}
+
 fn color_to_termcolor(color: Color) -> TermColor {
     match color {
         Color::U => TermColor::DarkYellow,
@@ -194,6 +376,44 @@ fn main() {
             verbose,
             preview,
         }) => solve(scramble, facelet, *max, *timeout, *verbose, *preview),
+        Some(Commands::Analyze {
+            scramble,
+            facelet,
+            max,
+            timeout,
+        }) => analyze_state(scramble, facelet, *max, *timeout),
+        Some(Commands::Stats {
+            cubes,
+            seed,
+            max,
+            timeout,
+            nodes,
+            single_threaded,
+            csv,
+            json,
+        }) => {
+            let options = StatsOptions {
+                cubes: *cubes,
+                seed: *seed,
+                limits: SearchLimits {
+                    max_length: *max,
+                    timeout: Some(*timeout),
+                    max_nodes: *nodes,
+                    ..Default::default()
+                },
+                single_threaded: *single_threaded,
+            };
+            stats(&options, csv, json)
+        }
+        Some(Commands::Cross { scramble, xcross }) => cross(scramble, *xcross),
+        Some(Commands::Bld { scramble }) => bld(scramble),
+        Some(Commands::Insert {
+            scramble,
+            skeleton,
+            insertions,
+            count,
+        }) => insert(scramble, skeleton, *insertions, *count),
+        Some(Commands::Reconstruct { scramble, solution }) => reconstruction(scramble, solution),
         Some(Commands::Scramble { length, preview }) => scramble(*length, *preview),
         _ => Ok(()),
     };
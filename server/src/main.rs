@@ -1,15 +1,21 @@
+use std::sync::Arc;
+
 use axum::Json;
 use axum::{
-    extract::Path,
-    http::{HeaderValue, Method},
+    extract::{Path, State},
+    http::{HeaderValue, Method, StatusCode},
     response::Html,
     routing::get,
     Router,
 };
 use tower_http::cors::CorsLayer;
 
+use kociemba::analysis::{self, ScrambleReport};
+use kociemba::async_solver::AsyncSolver;
+use kociemba::cubie::CubieCube;
+use kociemba::facelet::FaceCube;
 use kociemba::scramble;
-use kociemba::solver::{self, SoutionResult};
+use kociemba::solver::{SearchLimits, SoutionResult};
 
 #[tokio::main]
 async fn main() {
@@ -17,11 +23,18 @@ async fn main() {
     let cors = CorsLayer::new()
         .allow_origin("http://127.0.0.1:8080".parse::<HeaderValue>().unwrap())
         .allow_methods([Method::GET]);
+    // solve on a dedicated thread pool, at most 4 cubes at once
+    let solver = Arc::new(AsyncSolver::new(
+        std::thread::available_parallelism().map_or(1, |n| n.get()),
+        4,
+    ));
     let app = Router::new()
         .route("/", get(index))
-        .route("/solve/:puzzle", get(move |p| solve(p)))
+        .route("/solve/:puzzle", get(solve))
+        .route("/analyze/:puzzle", get(analyze))
         .route("/scramble", get(scramble))
-        .layer(cors);
+        .layer(cors)
+        .with_state(solver);
 
     let app = app.fallback(index);
 
@@ -36,6 +49,7 @@ async fn main() {
 async fn index() -> Html<&'static str> {
     Html("<p>Solve a cube: http://localhost:32125/solve/<Facelet String></p>
     Example: <a href=\"http://localhost:32125/solve/DUUBULDBFRBFRRULLLBRDFFFBLURDBFDFDRFRULBLUFDURRBLBDUDL\">http://localhost:32125/solve/DUUBULDBFRBFRRULLLBRDFFFBLURDBFDFDRFRULBLUFDURRBLBDUDL</a>
+    <p>Analyze a cube: http://localhost:32125/analyze/<Facelet String></p>
     <p>Get a scramble: <a href=\"http://localhost:32125/scramble\">http://localhost:32125/scramble</a></p>")
 }
 
@@ -44,11 +58,33 @@ async fn scramble() -> String {
     format!("Scramble: {}", scramble::scramble_to_str(&ss).unwrap())
 }
 
-async fn solve(Path(puzzle): Path<String>) -> Json<SoutionResult> {
-    let result = solver::solve(&puzzle, 20, 3.0).unwrap();
-    Json(result)
-    // match result {
-    //     Ok(solution) => Json(solution),
-    //     error => Json(error),
-    // }
+async fn solve(
+    State(solver): State<Arc<AsyncSolver>>,
+    Path(puzzle): Path<String>,
+) -> Result<Json<SoutionResult>, (StatusCode, String)> {
+    match solver.solve(&puzzle, 20, 3.0).await {
+        Ok(solution) => Ok(Json(solution)),
+        Err(error) => Err((StatusCode::BAD_REQUEST, error.to_string())),
+    }
+}
+
+async fn analyze(
+    State(solver): State<Arc<AsyncSolver>>,
+    Path(puzzle): Path<String>,
+) -> Result<Json<ScrambleReport>, (StatusCode, String)> {
+    let report = async {
+        let cc = CubieCube::try_from(&FaceCube::try_from(puzzle.as_str())?)?;
+        // search the shortest solution within one second
+        let limits = SearchLimits {
+            max_length: 0,
+            timeout: Some(1.0),
+            ..Default::default()
+        };
+        let result = solver.solve_with_limits(&puzzle, &limits).await?;
+        analysis::analyze_with_solution(&cc, result.solution)
+    };
+    match report.await {
+        Ok(report) => Ok(Json(report)),
+        Err(error) => Err((StatusCode::BAD_REQUEST, error.to_string())),
+    }
 }
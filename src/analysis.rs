@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::facelet::{FaceCube, CORNER_FACELET, EDGE_FACELET};
+use crate::moves::Move;
+use crate::phases::{self, Distances};
+use crate::scramble::scramble_to_str;
+use crate::solver::{self, SearchLimits};
+use crate::symmetries;
+
+/// A count for each of the three axes of the cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AxisCounts {
+    pub ud: u8,
+    pub rl: u8,
+    pub fb: u8,
+}
+
+impl fmt::Display for AxisCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UD {}, RL {}, FB {}", self.ud, self.rl, self.fb)
+    }
+}
+
+/// Analysis of a scrambled cube.
+///
+/// * `solution`: The best solution found, see [analyze].
+/// * `best_length`: The length of the solution.
+/// * `optimal`: The solution is known to be optimal, its length equals the lower bound.
+/// * `distances`: The [Distances] from the pruning tables, `distances.phase1` is a lower bound for the length.
+/// * `misoriented_edges`: Number of edges which are not oriented with respect to the axis: for an axis the quarter
+///   turns of its two faces flip edges, e.g. only F and B quarter turns change the FB edge orientation.
+/// * `misoriented_corners`: Number of corners whose sticker of the axis colors is not on a face of the axis.
+/// * `pairs`: Number of connected corner-edge pairs (1x1x2 blocks), wherever they are.
+/// * `solved_corners`, `solved_edges`: Number of pieces in their home position with correct orientation.
+/// * `solved_blocks`: Number of 2x2x2 blocks solved in place.
+/// * `odd_parity`: The corner (and edge) permutation is odd.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScrambleReport {
+    pub solution: Vec<Move>,
+    pub best_length: usize,
+    pub optimal: bool,
+    pub distances: Distances,
+    pub misoriented_edges: AxisCounts,
+    pub misoriented_corners: AxisCounts,
+    pub pairs: u8,
+    pub solved_corners: u8,
+    pub solved_edges: u8,
+    pub solved_blocks: u8,
+    pub odd_parity: bool,
+}
+
+impl fmt::Display for ScrambleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let solution = scramble_to_str(&self.solution).map_err(|_| fmt::Error)?;
+        let optimal = if self.optimal { " (optimal)" } else { "" };
+        writeln!(f, "Solution: {}", solution)?;
+        writeln!(f, "Best length: {}{}", self.best_length, optimal)?;
+        writeln!(f, "Phase 1 distance: {}", self.distances.phase1)?;
+        writeln!(f, "Misoriented edges: {}", self.misoriented_edges)?;
+        writeln!(f, "Misoriented corners: {}", self.misoriented_corners)?;
+        writeln!(f, "Pairs: {}", self.pairs)?;
+        writeln!(f, "Solved corners: {}, edges: {}", self.solved_corners, self.solved_edges)?;
+        writeln!(f, "Solved 2x2x2 blocks: {}", self.solved_blocks)?;
+        write!(f, "Parity: {}", if self.odd_parity { "odd" } else { "even" })
+    }
+}
+
+/// Analyze the cube cc, the best solution is searched within limits.
+///
+/// The search also stops at a solution with the length of the lower bound `distances.phase1`, which is optimal.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::analysis::analyze;
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::scramble::scramble_from_str;
+/// use kociemba::solver::SearchLimits;
+///
+/// fn main() {
+///     let cc = CubieCube::from(&scramble_from_str("R U R' U' F2 D L2").unwrap());
+///     let report = analyze(&cc, &SearchLimits::default()).unwrap();
+///     println!("{}", report);
+/// }
+/// ```
+pub fn analyze(cc: &CubieCube, limits: &SearchLimits) -> Result<ScrambleReport, Error> {
+    let limits = SearchLimits {
+        max_length: limits.max_length.max(phases::distances(cc)?.phase1 as usize),
+        ..*limits
+    };
+    let result = solver::solve_cubie(cc, &CubieCube::default(), &limits)?;
+    analyze_with_solution(cc, result.solution)
+}
+
+/// Analyze the cube cc with an already known solution, e.g. from [crate::async_solver::AsyncSolver].
+pub fn analyze_with_solution(cc: &CubieCube, solution: Vec<Move>) -> Result<ScrambleReport, Error> {
+    let distances = phases::distances(cc)?;
+    let fc = FaceCube::try_from(cc)?;
+    let best_length = solution.len();
+    Ok(ScrambleReport {
+        solution,
+        best_length,
+        optimal: best_length == distances.phase1 as usize,
+        distances,
+        misoriented_edges: misoriented_edges(cc),
+        misoriented_corners: misoriented_corners(cc),
+        pairs: pairs(&fc),
+        solved_corners: (0..8).filter(|i| corner_solved(cc, *i)).count() as u8,
+        solved_edges: (0..12).filter(|i| edge_solved(cc, *i)).count() as u8,
+        solved_blocks: solved_blocks(cc),
+        odd_parity: !cc.corner_parity(),
+    })
+}
+
+/// Give the cube seen with the axes permuted: rot = 1 maps the RL axis to UD, rot = 2 maps the FB axis to UD.
+fn rotate_axes(cc: &CubieCube, rot: usize) -> CubieCube {
+    let sc = symmetries::sc();
+    // conjugation by the 120° rotation around the URF-DBL diagonal (S_URF3), as in the solver
+    let (a, b) = match rot {
+        1 => (32, 16),
+        2 => (16, 32),
+        _ => return *cc,
+    };
+    let mut d = sc[a];
+    d.multiply(*cc);
+    d.multiply(sc[b]);
+    d
+}
+
+/// Number of misoriented edges for each axis.
+///
+/// The edge orientation of [CubieCube] is the one of the FB axis.
+pub fn misoriented_edges(cc: &CubieCube) -> AxisCounts {
+    let count = |rot| rotate_axes(cc, rot).eo.iter().filter(|o| **o != 0).count() as u8;
+    AxisCounts {
+        ud: count(2),
+        rl: count(1),
+        fb: count(0),
+    }
+}
+
+/// Number of misoriented corners for each axis.
+///
+/// The corner orientation of [CubieCube] is the one of the UD axis.
+pub fn misoriented_corners(cc: &CubieCube) -> AxisCounts {
+    let count = |rot| rotate_axes(cc, rot).co.iter().filter(|o| **o != 0).count() as u8;
+    AxisCounts {
+        ud: count(0),
+        rl: count(2),
+        fb: count(1),
+    }
+}
+
+fn corner_solved(cc: &CubieCube, i: usize) -> bool {
+    cc.cp[i] as usize == i && cc.co[i] == 0
+}
+
+fn edge_solved(cc: &CubieCube, i: usize) -> bool {
+    cc.ep[i] as usize == i && cc.eo[i] == 0
+}
+
+/// Number of 2x2x2 blocks solved in place: a corner with its three adjacent edges.
+fn solved_blocks(cc: &CubieCube) -> u8 {
+    (0..8)
+        .filter(|c| corner_solved(cc, *c) && junctions(*c).iter().all(|e| edge_solved(cc, *e)))
+        .count() as u8
+}
+
+/// Give the edge positions next to the corner position c.
+fn junctions(c: usize) -> Vec<usize> {
+    (0..12).filter(|e| adjacent_facelets(c, *e).is_some()).collect()
+}
+
+/// If the corner position c and the edge position e are next to each other, give the two pairs of facelets
+/// (corner facelet, edge facelet) which touch each other on a face.
+fn adjacent_facelets(c: usize, e: usize) -> Option<[(usize, usize); 2]> {
+    let mut touching = Vec::new();
+    for fe in EDGE_FACELET[e] {
+        let fe = fe as usize;
+        for fc in CORNER_FACELET[c] {
+            let fc = fc as usize;
+            let (rc, cc_) = ((fc % 9) / 3, (fc % 9) % 3);
+            let (re, ce) = ((fe % 9) / 3, (fe % 9) % 3);
+            if fc / 9 == fe / 9 && rc.abs_diff(re) + cc_.abs_diff(ce) == 1 {
+                touching.push((fc, fe));
+            }
+        }
+    }
+    match touching[..] {
+        [a, b] => Some([a, b]),
+        _ => None,
+    }
+}
+
+/// Number of corner-edge pairs which are connected: both touching facelet pairs have the same color.
+fn pairs(fc: &FaceCube) -> u8 {
+    let mut n = 0;
+    for c in 0..8 {
+        for e in 0..12 {
+            if let Some(touching) = adjacent_facelets(c, e) {
+                if touching.iter().all(|(a, b)| fc.f[*a] == fc.f[*b]) {
+                    n += 1;
+                }
+            }
+        }
+    }
+    n
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::*;
+    use crate::moves::Move::*;
+
+    #[test]
+    fn test_orientation() {
+        let axes = |moves: &[Move]| {
+            let cc = CubieCube::default().apply_moves(moves);
+            (misoriented_edges(&cc), misoriented_corners(&cc))
+        };
+        let four = |ud, rl, fb| AxisCounts { ud, rl, fb };
+        assert_eq!(axes(&[U]), (four(4, 0, 0), four(0, 4, 4)));
+        assert_eq!(axes(&[R]), (four(0, 4, 0), four(4, 0, 4)));
+        assert_eq!(axes(&[F]), (four(0, 0, 4), four(4, 4, 0)));
+        assert_eq!(axes(&[U2, R2, F2]), (four(0, 0, 0), four(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_analyze() {
+        let report = analyze_with_solution(&CubieCube::default(), Vec::new()).unwrap();
+        assert_eq!(report.pairs, 24);
+        assert_eq!(report.solved_blocks, 8);
+        assert_eq!((report.solved_corners, report.solved_edges), (8, 12));
+        assert!(report.optimal);
+        assert!(!report.odd_parity);
+
+        let limits = SearchLimits {
+            max_length: 0,
+            max_nodes: Some(100_000),
+            ..Default::default()
+        };
+        let cc = CubieCube::default().apply_moves(&[R]);
+        let report = analyze(&cc, &limits).unwrap();
+        assert_eq!(report.solution, vec![R3]);
+        assert!(report.optimal);
+        assert!(report.odd_parity);
+        // the L half of the cube is untouched, only the pairs between R layer and M layer are broken
+        assert_eq!(report.solved_blocks, 4);
+        assert_eq!(report.pairs, 20);
+        assert_eq!((report.solved_corners, report.solved_edges), (4, 8));
+
+        let cc = CubieCube::default().apply_moves(&[R, U, R3, U3]);
+        let report = analyze(&cc, &limits).unwrap();
+        assert_eq!(report.best_length, 4);
+        assert_eq!(cc.apply_moves(&report.solution), CubieCube::default());
+        assert!(!report.odd_parity);
+        assert!(report.to_string().contains("Best length: 4"));
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<ScrambleReport>(&json).unwrap(), report);
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use tokio::sync::{oneshot, Semaphore};
+
+use crate::batch::SolverPool;
+use crate::error::Error;
+use crate::solver::{self, SearchLimits, SharedSearch, SoutionResult, GOALSTRING};
+
+/// Async front end of a [SolverPool] for tokio based services.
+///
+/// The search never runs on the async runtime, it runs on the worker threads of the pool. At most
+/// `max_concurrent` cubes are searched at the same time, further calls wait for a free slot.
+/// Dropping the future returned by [AsyncSolver::solve] stops the search of its cube.
+///
+/// Needs the `async` feature.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::async_solver::AsyncSolver;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let solver = AsyncSolver::new(4, 2);
+///     let result = solver
+///         .solve("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF", 20, 3.0)
+///         .await
+///         .unwrap();
+///     println!("{:?}", result.solution);
+/// }
+/// ```
+pub struct AsyncSolver {
+    pool: SolverPool,
+    limit: Semaphore,
+}
+
+impl AsyncSolver {
+    /// Create a solver with `threads` worker threads which searches at most `max_concurrent` cubes at once.
+    pub fn new(threads: usize, max_concurrent: usize) -> Self {
+        Self {
+            pool: SolverPool::new(threads),
+            limit: Semaphore::new(max_concurrent.max(1)),
+        }
+    }
+
+    /// Solve a cube defined by its cube definition string, see [solver::solve].
+    pub async fn solve(
+        &self,
+        cubestring: &str,
+        max_length: usize,
+        timeout: f32,
+    ) -> Result<SoutionResult, Error> {
+        self.solver(cubestring, GOALSTRING, max_length, timeout).await
+    }
+
+    /// Solve a cube defined by cubestring to a position defined by goalstring, see [solver::solver].
+    pub async fn solver(
+        &self,
+        cubestring: &str,
+        goalstring: &str,
+        max_length: usize,
+        timeout: f32,
+    ) -> Result<SoutionResult, Error> {
+        let limits = SearchLimits {
+            max_length,
+            timeout: Some(timeout),
+            ..Default::default()
+        };
+        self.solver_with_limits(cubestring, goalstring, &limits).await
+    }
+
+    /// Solve a cube defined by its cube definition string within the given [SearchLimits].
+    pub async fn solve_with_limits(
+        &self,
+        cubestring: &str,
+        limits: &SearchLimits,
+    ) -> Result<SoutionResult, Error> {
+        self.solver_with_limits(cubestring, GOALSTRING, limits).await
+    }
+
+    /// Solve a cube defined by cubestring to a position defined by goalstring within the given [SearchLimits],
+    /// see [solver::solver_with_limits].
+    pub async fn solver_with_limits(
+        &self,
+        cubestring: &str,
+        goalstring: &str,
+        limits: &SearchLimits,
+    ) -> Result<SoutionResult, Error> {
+        let (start, goal) = solver::parse_cubes(cubestring, goalstring)?;
+        let _permit = self.limit.acquire().await.unwrap(); // the semaphore is never closed
+        let (sender, receiver) = oneshot::channel();
+        let shared = self.pool.submit(start, goal, *limits, move |result| {
+            let _ = sender.send(result);
+        })?;
+        let mut guard = CancelOnDrop {
+            shared,
+            done: false,
+        };
+        let result = receiver.await;
+        guard.done = true;
+        // the sender is dropped without a result if the searches never finished
+        result.unwrap_or(Err(Error::SearchFailed))
+    }
+}
+
+/// Stops the searches of a cube if the future waiting for them is dropped.
+struct CancelOnDrop {
+    shared: Arc<SharedSearch>,
+    done: bool,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if !self.done {
+            self.shared.terminated.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use crate::async_solver::*;
+    use crate::cubie::CubieCube;
+    use crate::facelet::FaceCube;
+    use crate::patterns::Pattern;
+
+    #[tokio::test]
+    async fn test_solve() {
+        let solver = AsyncSolver::new(2, 1);
+        let result = solver
+            .solve("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF", 20, 3.0)
+            .await
+            .unwrap();
+        assert!(result.solution.len() <= 20);
+        assert!(solver.solve("UUU", 20, 3.0).await.is_err());
+
+        // the solution is checked against the cube and the goal
+        let limits = SearchLimits {
+            self_check: true,
+            ..Default::default()
+        };
+        let goal = Pattern::CubeInCube.cube();
+        let goalstring = FaceCube::try_from(&goal).unwrap().to_string();
+        let result = solver
+            .solver_with_limits(
+                "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF",
+                &goalstring,
+                &limits,
+            )
+            .await
+            .unwrap();
+        let fc = FaceCube::try_from("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF").unwrap();
+        let cc = CubieCube::try_from(&fc).unwrap();
+        assert_eq!(cc.apply_moves(&result.solution), goal);
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let solver = AsyncSolver::new(1, 1);
+        // a maneuver of length 0 is never found, so only the timeout would stop this search
+        let search = solver.solve("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF", 0, 60.0);
+        assert!(tokio::time::timeout(Duration::from_millis(100), search).await.is_err());
+
+        let start = Instant::now();
+        let result = solver
+            .solve("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF", 20, 3.0)
+            .await
+            .unwrap();
+        assert!(result.solution.len() <= 20);
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+}
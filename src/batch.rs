@@ -0,0 +1,403 @@
+use std::cmp::max;
+use std::iter::Enumerate;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::solver::{
+    self, SearchLimits, SharedSearch, SolverThread, SoutionResult, GOALSTRING, SOLVERTABLES,
+};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+type BatchResult = (usize, Result<SoutionResult, Error>);
+type OnDone = Box<dyn FnOnce(Result<SoutionResult, Error>) + Send + 'static>;
+
+/// A pool of worker threads for solving many cubes.
+///
+/// [solver::solver] starts up to six threads for every cube, one for each rotation/inversion of the cube.
+/// The pool runs these searches as jobs on a fixed number of threads instead, which are reused for all cubes
+/// and are stopped when the pool is dropped.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::batch::SolverPool;
+///
+/// fn main() {
+///     let pool = SolverPool::new(4);
+///     let results = pool.solve_batch(
+///         &[
+///             "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF",
+///             "DUUBULDBFRBFRRULLLBRDFFFBLURDBFDFDRFRULBLUFDURRBLBDUDL",
+///         ],
+///         20,
+///         3.0,
+///     );
+///     for result in results {
+///         println!("{:?}", result.unwrap().solution);
+///     }
+/// }
+/// ```
+pub struct SolverPool {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Default for SolverPool {
+    /// Create a pool with one thread per available CPU.
+    fn default() -> Self {
+        Self::new(thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+}
+
+impl SolverPool {
+    /// Create a pool with `threads` worker threads (at least one).
+    ///
+    /// The data tables are loaded here if this has not happened yet, so solve times do not include it.
+    pub fn new(threads: usize) -> Self {
+        lazy_static::initialize(&SOLVERTABLES);
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..max(threads, 1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        // a panicking job must not stop the worker
+                        Ok(job) => {
+                            let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                        }
+                        Err(_) => break, // pool dropped
+                    }
+                })
+            })
+            .collect();
+        Self {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    /// Number of worker threads.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Solve the cubes defined by cubestrings. The results are returned in the order of the cubes.
+    ///
+    /// `max_length` and `timeout` apply to every single cube as in [solver::solve], the timeout of a cube
+    /// starts when the first search for it starts. An invalid cube definition gives an error for this cube only.
+    pub fn solve_batch<S: AsRef<str>>(
+        &self,
+        cubestrings: &[S],
+        max_length: usize,
+        timeout: f32,
+    ) -> Vec<Result<SoutionResult, Error>> {
+        self.solve_batch_with_limits(cubestrings, &timeout_limits(max_length, timeout))
+    }
+
+    /// Solve the cubes defined by cubestrings within the given [SearchLimits], see [SolverPool::solve_batch].
+    ///
+    /// A node budget bounds the CPU time spent on every cube independent of the machine load.
+    pub fn solve_batch_with_limits<S: AsRef<str>>(
+        &self,
+        cubestrings: &[S],
+        limits: &SearchLimits,
+    ) -> Vec<Result<SoutionResult, Error>> {
+        let mut results: Vec<Option<Result<SoutionResult, Error>>> =
+            (0..cubestrings.len()).map(|_| None).collect();
+        for (i, result) in self.solve_unordered_with_limits(cubestrings, limits) {
+            results[i] = Some(result);
+        }
+        results.into_iter().map(|r| r.unwrap()).collect()
+    }
+
+    /// Solve the cubes defined by cubestrings, yielding `(index, result)` pairs in the order the cubes are solved.
+    ///
+    /// The cubes are taken from the iterator only as the workers need them, so arbitrarily long
+    /// (also lazily generated) sequences of cubes can be solved.
+    pub fn solve_unordered<I>(
+        &self,
+        cubestrings: I,
+        max_length: usize,
+        timeout: f32,
+    ) -> BatchResults<'_, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.solve_unordered_with_limits(cubestrings, &timeout_limits(max_length, timeout))
+    }
+
+    /// Solve the cubes defined by cubestrings within the given [SearchLimits], see [SolverPool::solve_unordered].
+    pub fn solve_unordered_with_limits<I>(
+        &self,
+        cubestrings: I,
+        limits: &SearchLimits,
+    ) -> BatchResults<'_, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let (sender, receiver) = mpsc::channel();
+        BatchResults {
+            pool: self,
+            cubestrings: cubestrings.into_iter().enumerate(),
+            limits: *limits,
+            sender,
+            receiver,
+            in_flight: 0,
+        }
+    }
+
+    /// Queue the searches for the maneuver from the cube start to the cube goal, `on_done` is called with the result
+    /// when all of them are done.
+    ///
+    /// Returns the state shared by the searches, setting its terminated flag stops them.
+    pub(crate) fn submit<F>(
+        &self,
+        start: CubieCube,
+        goal: CubieCube,
+        limits: SearchLimits,
+        on_done: F,
+    ) -> Result<Arc<SharedSearch>, Error>
+    where
+        F: FnOnce(Result<SoutionResult, Error>) + Send + 'static,
+    {
+        let cc = solver::prepare_cubie(&start, &goal)?;
+        let directions = solver::search_directions(&cc);
+        let shared = Arc::new(SharedSearch::default());
+        let item = Arc::new(BatchItem {
+            cc,
+            start,
+            goal,
+            limits,
+            remaining: Mutex::new(directions.len()),
+            start_time: Mutex::new(None),
+            failed: AtomicBool::new(false),
+            shared: Arc::clone(&shared),
+            on_done: Mutex::new(Some(Box::new(on_done))),
+        });
+        for d in directions {
+            let item = Arc::clone(&item);
+            self.execute(Box::new(move || {
+                let search = panic::catch_unwind(AssertUnwindSafe(|| {
+                    let start_time = *item
+                        .start_time
+                        .lock()
+                        .unwrap()
+                        .get_or_insert_with(Instant::now);
+                    let mut sth = SolverThread::new(
+                        item.cc,
+                        d % 3,
+                        d / 3,
+                        limits,
+                        Some(start_time),
+                        Arc::clone(&item.shared),
+                        &SOLVERTABLES,
+                    );
+                    sth.start();
+                }));
+                if search.is_err() {
+                    item.failed.store(true, Ordering::Relaxed);
+                }
+                item.finish();
+            }));
+        }
+        Ok(shared)
+    }
+
+    fn execute(&self, job: Job) {
+        // the workers only stop after the sender is dropped
+        self.sender.as_ref().unwrap().send(job).unwrap();
+    }
+}
+
+impl Drop for SolverPool {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// The limits of [SolverPool::solve_batch] and [SolverPool::solve_unordered].
+fn timeout_limits(max_length: usize, timeout: f32) -> SearchLimits {
+    SearchLimits {
+        max_length,
+        timeout: Some(timeout),
+        ..Default::default()
+    }
+}
+
+/// State of one cube shared by its searches.
+///
+/// `cc` is the cube which the searches solve to the identity, `start` and `goal` the cubes it is prepared from.
+struct BatchItem {
+    cc: CubieCube,
+    start: CubieCube,
+    goal: CubieCube,
+    limits: SearchLimits,
+    remaining: Mutex<usize>,
+    start_time: Mutex<Option<Instant>>,
+    /// Set if one of the searches panicked, the cube then gives [Error::SearchFailed].
+    failed: AtomicBool,
+    shared: Arc<SharedSearch>,
+    on_done: Mutex<Option<OnDone>>,
+}
+
+impl BatchItem {
+    /// Called by every search when it is done, the last one reports the result.
+    fn finish(&self) {
+        let mut remaining = self.remaining.lock().unwrap();
+        *remaining -= 1;
+        if *remaining == 0 {
+            let start_time = self.start_time.lock().unwrap().unwrap();
+            let result = match self.failed.load(Ordering::Relaxed) {
+                true => Err(Error::SearchFailed),
+                false => solver::checked_result(
+                    &self.shared,
+                    &self.start,
+                    &self.goal,
+                    &self.limits,
+                    start_time.elapsed(),
+                ),
+            };
+            if let Some(on_done) = self.on_done.lock().unwrap().take() {
+                on_done(result);
+            }
+        }
+    }
+}
+
+/// Iterator over the results of [SolverPool::solve_unordered].
+///
+/// Keeps twice as many cubes queued as the pool has threads, so the workers never run idle.
+pub struct BatchResults<'p, I> {
+    pool: &'p SolverPool,
+    cubestrings: Enumerate<I>,
+    limits: SearchLimits,
+    sender: Sender<BatchResult>,
+    receiver: Receiver<BatchResult>,
+    in_flight: usize,
+}
+
+impl<I> Iterator for BatchResults<'_, I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = (usize, Result<SoutionResult, Error>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.in_flight < 2 * self.pool.threads() {
+            let Some((i, cubestring)) = self.cubestrings.next() else {
+                break;
+            };
+            let (start, goal) = match solver::parse_cubes(cubestring.as_ref(), GOALSTRING) {
+                Ok(cubes) => cubes,
+                Err(e) => return Some((i, Err(e))),
+            };
+            let sender = self.sender.clone();
+            let submitted = self.pool.submit(start, goal, self.limits, move |result| {
+                let _ = sender.send((i, result));
+            });
+            if let Err(e) = submitted {
+                return Some((i, Err(e)));
+            }
+            self.in_flight += 1;
+        }
+        if self.in_flight == 0 {
+            return None;
+        }
+        let result = self.receiver.recv().unwrap();
+        self.in_flight -= 1;
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::batch::*;
+    use crate::facelet::FaceCube;
+
+    #[test]
+    fn test_solve_batch() {
+        let cubestrings = [
+            "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF",
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBX",
+            "DUUBULDBFRBFRRULLLBRDFFFBLURDBFDFDRFRULBLUFDURRBLBDUDL",
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB",
+        ];
+        let pool = SolverPool::new(2);
+        let results = pool.solve_batch(&cubestrings, 20, 3.0);
+        assert_eq!(results.len(), 4);
+        assert!(results[1].is_err());
+        for i in [0, 2, 3] {
+            let solution = &results[i].as_ref().unwrap().solution;
+            assert!(solution.len() <= 20);
+            let fc = FaceCube::try_from(cubestrings[i]).unwrap();
+            let cc = CubieCube::try_from(&fc).unwrap();
+            assert_eq!(cc.apply_moves(solution), CubieCube::default());
+        }
+        assert!(results[3].as_ref().unwrap().solution.is_empty());
+
+        // the pool is reused
+        let results: Vec<_> = pool.solve_unordered(&cubestrings[..1], 20, 3.0).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 0);
+
+        let limits = SearchLimits {
+            max_length: 0,
+            max_nodes: Some(10_000),
+            ..Default::default()
+        };
+        let results = pool.solve_batch_with_limits(&cubestrings, &limits);
+        assert!(results[0].as_ref().unwrap().nodes > 10_000);
+        assert!(results[1].is_err());
+
+        // the solutions are checked against the cubes
+        let limits = SearchLimits {
+            self_check: true,
+            ..Default::default()
+        };
+        let results = pool.solve_batch_with_limits(&cubestrings, &limits);
+        assert!(results[0].is_ok() && results[2].is_ok());
+        assert!(results[3].as_ref().unwrap().solution.is_empty());
+    }
+
+    #[test]
+    fn test_panicking_search() {
+        // a panicking job does not stop the worker
+        let pool = SolverPool::new(1);
+        pool.execute(Box::new(|| panic!("job panicked")));
+        let results = pool.solve_batch(
+            &["UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB"],
+            20,
+            3.0,
+        );
+        assert!(results[0].as_ref().unwrap().solution.is_empty());
+
+        // the cube of a panicked search still reports
+        let (sender, receiver) = mpsc::channel();
+        let item = BatchItem {
+            cc: CubieCube::default(),
+            start: CubieCube::default(),
+            goal: CubieCube::default(),
+            limits: SearchLimits::default(),
+            remaining: Mutex::new(1),
+            start_time: Mutex::new(Some(Instant::now())),
+            failed: AtomicBool::new(true),
+            shared: Arc::new(SharedSearch::default()),
+            on_done: Mutex::new(Some(Box::new(move |result| sender.send(result).unwrap()))),
+        };
+        item.finish();
+        assert!(matches!(receiver.recv().unwrap(), Err(Error::SearchFailed)));
+    }
+}
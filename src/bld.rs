@@ -0,0 +1,375 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::constants::{ALL_CORNERS, ALL_EDGES};
+use crate::cubie::{Corner, CubieCube, Edge};
+use crate::error::Error;
+use crate::facelet::{Facelet, CORNER_FACELET, EDGE_FACELET};
+use crate::moves::Move;
+use crate::scramble::scramble_to_str;
+use crate::solver::{self, SearchLimits};
+
+/// Letters of the stickers, `corners[c][k]` is the letter of the sticker `CORNER_FACELET[c][k]` and
+/// `edges[e][k]` of `EDGE_FACELET[e][k]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LetterScheme {
+    pub corners: [[char; 3]; 8],
+    pub edges: [[char; 2]; 12],
+}
+
+impl Default for LetterScheme {
+    /// The Speffz scheme: the letters A to X go face by face (U, L, F, R, B, D) clockwise around each face, starting
+    /// at the top left corner and at the top edge.
+    fn default() -> Self {
+        Self {
+            corners: CORNER_FACELET.map(|facelets| facelets.map(speffz)),
+            edges: EDGE_FACELET.map(|facelets| facelets.map(speffz)),
+        }
+    }
+}
+
+fn speffz(f: Facelet) -> char {
+    let face = match f as usize / 9 {
+        0 => 0, // U
+        4 => 1, // L
+        2 => 2, // F
+        1 => 3, // R
+        5 => 4, // B
+        _ => 5, // D
+    };
+    let slot = match f as usize % 9 {
+        0 | 1 => 0,
+        2 | 5 => 1,
+        8 | 7 => 2,
+        _ => 3,
+    };
+    (b'A' + 4 * face + slot) as char
+}
+
+/// Options of [trace].
+///
+/// * `corner_buffer`, `edge_buffer`: The buffer pieces, the first sticker of the piece (the U or D sticker, the F or B
+///   sticker of the middle layer edges) is the buffer sticker.
+/// * `corner_algorithms`, `edge_algorithms`: Algorithms by letter pair, e.g. 3-style commutators, which are added to the
+///   pairs of the memo. [corner_pair_algorithm] and [edge_pair_algorithm] give an algorithm for any pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BldOptions {
+    pub corner_buffer: Corner,
+    pub edge_buffer: Edge,
+    pub scheme: LetterScheme,
+    pub corner_algorithms: HashMap<String, Vec<Move>>,
+    pub edge_algorithms: HashMap<String, Vec<Move>>,
+}
+
+impl Default for BldOptions {
+    fn default() -> Self {
+        Self {
+            corner_buffer: Corner::URF,
+            edge_buffer: Edge::UF,
+            scheme: LetterScheme::default(),
+            corner_algorithms: HashMap::new(),
+            edge_algorithms: HashMap::new(),
+        }
+    }
+}
+
+/// Two targets of the memo, or a single one at the end of an odd memo, with the algorithm from the options if there is
+/// one for the pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LetterPair {
+    pub letters: String,
+    pub algorithm: Option<Vec<Move>>,
+}
+
+/// The blindfolded memo of a cube.
+///
+/// * `edges`, `corners`: The targets in the order they are solved from the buffers, in letter pairs.
+/// * `flipped_edges`: The edges which are in place but flipped, the edge buffer last.
+/// * `twisted_corners`: The corners which are in place but twisted, with the twist 1 (clockwise) or 2
+///   (counter-clockwise), the corner buffer last.
+/// * `parity`: True for an odd number of targets, the buffers have to be swapped at the end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BldMemo {
+    pub edges: Vec<LetterPair>,
+    pub corners: Vec<LetterPair>,
+    pub flipped_edges: Vec<Edge>,
+    pub twisted_corners: Vec<(Corner, u8)>,
+    pub parity: bool,
+}
+
+impl fmt::Display for BldMemo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letters = |pairs: &[LetterPair]| {
+            let letters: Vec<&str> = pairs.iter().map(|p| p.letters.as_str()).collect();
+            letters.join(" ")
+        };
+        writeln!(f, "Edges: {}", letters(&self.edges))?;
+        writeln!(f, "Corners: {}", letters(&self.corners))?;
+        if !self.flipped_edges.is_empty() {
+            let edges: Vec<String> = self.flipped_edges.iter().map(|e| e.to_string()).collect();
+            writeln!(f, "Flipped edges: {}", edges.join(" "))?;
+        }
+        if !self.twisted_corners.is_empty() {
+            let corners: Vec<String> = self
+                .twisted_corners
+                .iter()
+                .map(|(c, t)| format!("{}{}", c, if *t == 1 { "+" } else { "-" }))
+                .collect();
+            writeln!(f, "Twisted corners: {}", corners.join(" "))?;
+        }
+        write!(f, "Parity: {}", if self.parity { "yes" } else { "no" })?;
+        for (name, pairs) in [("Edge", &self.edges), ("Corner", &self.corners)] {
+            for pair in pairs.iter() {
+                if let Some(alg) = &pair.algorithm {
+                    let alg = scramble_to_str(alg).map_err(|_| fmt::Error)?;
+                    write!(f, "\n{} {}: {}", name, pair.letters, alg)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The pieces of one type: the piece and its orientation at each position, and the letters of the stickers.
+struct Pieces<'a> {
+    state: Vec<(usize, u8)>,
+    n_ori: u8,
+    letters: Vec<&'a [char]>,
+}
+
+impl Pieces<'_> {
+    /// The sticker of the piece at position p which is on the facelet k of the position.
+    fn sticker(&self, p: usize, k: u8) -> u8 {
+        (k + self.n_ori - self.state[p].1) % self.n_ori
+    }
+
+    fn is_solved(&self, p: usize) -> bool {
+        self.state[p] == (p, 0)
+    }
+
+    /// Move the piece at the buffer to position p with the buffer sticker on the facelet k and the piece at p to the
+    /// buffer with the sticker of the facelet k on the buffer sticker.
+    fn shoot(&mut self, buffer: usize, (p, k): (usize, u8)) {
+        let n = self.n_ori;
+        let (j0, j1) = (self.sticker(buffer, 0), self.sticker(p, k));
+        let to_buffer = (self.state[p].0, (n - j1) % n);
+        self.state[p] = (self.state[buffer].0, (k + n - j0) % n);
+        self.state[buffer] = to_buffer;
+    }
+
+    /// Trace the pieces from the buffer: the letters of the targets and the misoriented pieces in place.
+    fn trace(&mut self, buffer: usize) -> (Vec<char>, Vec<(usize, u8)>) {
+        let mut misoriented: Vec<(usize, u8)> = (0..self.state.len())
+            .filter(|p| *p != buffer && self.state[*p].0 == *p && self.state[*p].1 != 0)
+            .map(|p| (p, self.state[p].1))
+            .collect();
+        // the stickers in the order of their letters for the cycle breaks
+        let mut stickers: Vec<(usize, u8)> = (0..self.state.len())
+            .flat_map(|p| (0..self.n_ori).map(move |k| (p, k)))
+            .collect();
+        stickers.sort_by_key(|(p, k)| self.letters[*p][*k as usize]);
+
+        let mut targets = Vec::new();
+        loop {
+            let piece = self.state[buffer].0;
+            let target = if piece != buffer {
+                (piece, self.sticker(buffer, 0))
+            } else {
+                // cycle break to the first unsolved piece
+                let unsolved = stickers.iter().find(|(p, _)| {
+                    *p != buffer && !self.is_solved(*p) && !misoriented.iter().any(|m| m.0 == *p)
+                });
+                match unsolved {
+                    Some(sticker) => *sticker,
+                    None => break,
+                }
+            };
+            targets.push(self.letters[target.0][target.1 as usize]);
+            self.shoot(buffer, target);
+        }
+        if self.state[buffer].1 != 0 {
+            misoriented.push((buffer, self.state[buffer].1));
+        }
+        (targets, misoriented)
+    }
+}
+
+fn letter_pairs(targets: &[char], algorithms: &HashMap<String, Vec<Move>>) -> Vec<LetterPair> {
+    targets
+        .chunks(2)
+        .map(|pair| {
+            let letters: String = pair.iter().collect();
+            LetterPair {
+                algorithm: algorithms.get(&letters).cloned(),
+                letters,
+            }
+        })
+        .collect()
+}
+
+/// Trace a cube into the blindfolded memo of the edges and the corners.
+///
+/// The targets are traced from the buffers, a cycle break goes to the unsolved piece with the first letter. Pieces
+/// which are in place but misoriented are not traced, they are listed as flipped edges and twisted corners.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::bld::{trace, BldOptions};
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::scramble::scramble_from_str;
+///
+/// fn main() {
+///     let cc = CubieCube::from(&scramble_from_str("R U R' U' F2 D L2 B' R2 U").unwrap());
+///     let memo = trace(&cc, &BldOptions::default()).unwrap();
+///     println!("{}", memo);
+/// }
+/// ```
+pub fn trace(cc: &CubieCube, options: &BldOptions) -> Result<BldMemo, Error> {
+    cc.verify()?;
+    let mut corners = Pieces {
+        state: (0..8).map(|i| (cc.cp[i] as usize, cc.co[i])).collect(),
+        n_ori: 3,
+        letters: options.scheme.corners.iter().map(|l| &l[..]).collect(),
+    };
+    let (corner_targets, twisted) = corners.trace(options.corner_buffer as usize);
+    let mut edges = Pieces {
+        state: (0..12).map(|i| (cc.ep[i] as usize, cc.eo[i])).collect(),
+        n_ori: 2,
+        letters: options.scheme.edges.iter().map(|l| &l[..]).collect(),
+    };
+    let (edge_targets, flipped) = edges.trace(options.edge_buffer as usize);
+    Ok(BldMemo {
+        edges: letter_pairs(&edge_targets, &options.edge_algorithms),
+        corners: letter_pairs(&corner_targets, &options.corner_algorithms),
+        flipped_edges: flipped.iter().map(|(e, _)| ALL_EDGES[*e]).collect(),
+        twisted_corners: twisted.iter().map(|(c, t)| (ALL_CORNERS[*c], *t)).collect(),
+        parity: edge_targets.len() % 2 == 1,
+    })
+}
+
+/// The position and the facelet index of the sticker with the letter.
+fn find_sticker(letters: &[&[char]], letter: char) -> Result<(usize, u8), Error> {
+    letters
+        .iter()
+        .enumerate()
+        .find_map(|(p, l)| l.iter().position(|c| *c == letter).map(|k| (p, k as u8)))
+        .ok_or(Error::InvalidLetterPair)
+}
+
+/// The cycle of the stickers buffer -> first letter -> second letter as the piece and the orientation of each position.
+fn pair_cycle(
+    letters: &[&[char]],
+    n_ori: u8,
+    buffer: usize,
+    pair: &str,
+) -> Result<Vec<(usize, u8)>, Error> {
+    let chars: Vec<char> = pair.chars().collect();
+    if chars.len() != 2 {
+        return Err(Error::InvalidLetterPair);
+    }
+    let (a, ka) = find_sticker(letters, chars[0])?;
+    let (b, kb) = find_sticker(letters, chars[1])?;
+    if a == b || a == buffer || b == buffer {
+        return Err(Error::InvalidLetterPair);
+    }
+    let mut state: Vec<(usize, u8)> = (0..letters.len()).map(|p| (p, 0)).collect();
+    state[a] = (buffer, ka);
+    state[b] = (a, (kb + n_ori - ka) % n_ori);
+    state[buffer] = (b, (n_ori - kb) % n_ori);
+    Ok(state)
+}
+
+/// Give an algorithm for a corner letter pair: the 3-cycle of the buffer sticker to the first and the first to the
+/// second sticker, found by the two-phase solver.
+pub fn corner_pair_algorithm(options: &BldOptions, pair: &str) -> Result<Vec<Move>, Error> {
+    let letters: Vec<&[char]> = options.scheme.corners.iter().map(|l| &l[..]).collect();
+    let cycle = pair_cycle(&letters, 3, options.corner_buffer as usize, pair)?;
+    let mut goal = CubieCube::default();
+    for (i, (c, o)) in cycle.into_iter().enumerate() {
+        goal.cp[i] = ALL_CORNERS[c];
+        goal.co[i] = o;
+    }
+    Ok(solver::solve_cubie(&CubieCube::default(), &goal, &SearchLimits::default())?.solution)
+}
+
+/// Give an algorithm for an edge letter pair, see [corner_pair_algorithm].
+pub fn edge_pair_algorithm(options: &BldOptions, pair: &str) -> Result<Vec<Move>, Error> {
+    let letters: Vec<&[char]> = options.scheme.edges.iter().map(|l| &l[..]).collect();
+    let cycle = pair_cycle(&letters, 2, options.edge_buffer as usize, pair)?;
+    let mut goal = CubieCube::default();
+    for (i, (e, o)) in cycle.into_iter().enumerate() {
+        goal.ep[i] = ALL_EDGES[e];
+        goal.eo[i] = o;
+    }
+    Ok(solver::solve_cubie(&CubieCube::default(), &goal, &SearchLimits::default())?.solution)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bld::*;
+    use crate::moves::Move::*;
+
+    #[test]
+    fn test_speffz() {
+        let scheme = LetterScheme::default();
+        assert_eq!(scheme.corners[Corner::ULB as usize], ['A', 'E', 'R']);
+        assert_eq!(scheme.corners[Corner::URF as usize], ['C', 'M', 'J']);
+        assert_eq!(scheme.edges[Edge::UF as usize], ['C', 'I']);
+        assert_eq!(scheme.edges[Edge::BL as usize], ['R', 'H']);
+        assert_eq!(scheme.edges[Edge::DB as usize], ['W', 'S']);
+    }
+
+    #[test]
+    fn test_trace() {
+        let options = BldOptions::default();
+        let memo = trace(&CubieCube::default(), &options).unwrap();
+        assert!(memo.edges.is_empty() && memo.corners.is_empty() && !memo.parity);
+
+        let memo = trace(&CubieCube::from(&vec![U]), &options).unwrap();
+        let letters = |pairs: &[LetterPair]| -> Vec<String> {
+            pairs.iter().map(|p| p.letters.clone()).collect()
+        };
+        assert_eq!(letters(&memo.edges), ["BA", "D"]);
+        assert_eq!(letters(&memo.corners), ["BA", "D"]);
+        assert!(memo.parity);
+
+        let mut cc = CubieCube::default();
+        cc.eo[Edge::UF as usize] = 1;
+        cc.eo[Edge::UR as usize] = 1;
+        cc.co[Corner::DFR as usize] = 1;
+        cc.co[Corner::DLF as usize] = 2;
+        let memo = trace(&cc, &options).unwrap();
+        assert!(memo.edges.is_empty());
+        assert_eq!(memo.flipped_edges, [Edge::UR, Edge::UF]);
+        assert_eq!(memo.twisted_corners, [(Corner::DFR, 1), (Corner::DLF, 2)]);
+        assert!(memo.to_string().contains("Twisted corners: DFR+ DLF-"));
+    }
+
+    #[test]
+    fn test_pair_algorithms() {
+        let mut options = BldOptions::default();
+        let cc = CubieCube::from(&vec![F2, R, U, D3, L, B2]);
+        let memo = trace(&cc, &options).unwrap();
+        assert!(!memo.parity && memo.flipped_edges.is_empty() && memo.twisted_corners.is_empty());
+        for pair in &memo.edges {
+            let alg = edge_pair_algorithm(&options, &pair.letters).unwrap();
+            options.edge_algorithms.insert(pair.letters.clone(), alg);
+        }
+        for pair in &memo.corners {
+            let alg = corner_pair_algorithm(&options, &pair.letters).unwrap();
+            options.corner_algorithms.insert(pair.letters.clone(), alg);
+        }
+        // the algorithms of the pairs solve the cube
+        let memo = trace(&cc, &options).unwrap();
+        let mut solved = cc;
+        for pair in memo.edges.iter().chain(&memo.corners) {
+            solved = solved.apply_moves(pair.algorithm.as_ref().unwrap());
+        }
+        assert_eq!(solved, CubieCube::default());
+
+        assert!(edge_pair_algorithm(&options, "CA").is_err());
+        assert!(edge_pair_algorithm(&options, "AQ").is_err());
+        assert!(corner_pair_algorithm(&options, "A").is_err());
+    }
+}
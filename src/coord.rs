@@ -1,10 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::{fmt, usize};
 
 use crate::constants::*;
 use crate::cubie::Edge::*;
-use crate::moves;
+use crate::moves::{self, MoveTables};
 use crate::symmetries::SymmetriesTables;
-use crate::symmetries;
 use crate::{cubie::CubieCube, error::Error};
 use crate::{decode_table, write_table};
 
@@ -15,7 +15,10 @@ use crate::{decode_table, write_table};
 /// 
 /// In phase 2 a state is uniquely determined by the three coordinates corners, ud_edges and slice_sorted % 24.
 /// 
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// Serialized as an object with one number per field, e.g. in JSON
+/// `{"twist":0,"flip":0,"slice_sorted":0,"u_edges":1656,"d_edges":0,"corners":0,"ud_edges":0,...}`.
+/// 
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct CoordCube {
     pub twist: u16,        // twist of corners
     pub flip: u16,         // flip of edges
@@ -114,22 +117,23 @@ impl CoordCube {
 
     /// Update phase 1 coordinates when move is apply.
     /// 
+    /// The move and symmetry tables are borrowed, e.g. from [crate::solver::solver_tables], so this is only
+    /// a few table lookups.
+    /// 
     /// :param m: The move
-    pub fn phase1_move(&mut self, m: moves::Move) -> Result<(), Error> {
-        let twist_move = moves::move_twist()?;
-        let flip_move = moves::move_flip()?;
-        let slice_sorted_move = moves::move_slice_sorted()?;
-        let u_edges_move = moves::move_u_edges()?;
-        let d_edges_move = moves::move_d_edges()?;
-        let corners_move = moves::move_corners()?;
-        let flipslicesyms = symmetries::flipslice_syms()?;
-        let flipslice_classidx = flipslicesyms.classidx;
-        let flipslice_sym = flipslicesyms.sym;
-        let flipslice_rep = flipslicesyms.rep;
-        let cornersyms = symmetries::corner_syms()?;
-        let corner_classidx = cornersyms.classidx;
-        let corner_sym = cornersyms.sym;
-        let corner_rep = cornersyms.rep;
+    pub fn phase1_move(&mut self, m: moves::Move, mv: &MoveTables, sy: &SymmetriesTables) {
+        let twist_move = &mv.twist_move;
+        let flip_move = &mv.flip_move;
+        let slice_sorted_move = &mv.slice_sorted_move;
+        let u_edges_move = &mv.u_edges_move;
+        let d_edges_move = &mv.d_edges_move;
+        let corners_move = &mv.corners_move;
+        let flipslice_classidx = &sy.flipslice_classidx;
+        let flipslice_sym = &sy.flipslice_sym;
+        let flipslice_rep = &sy.flipslice_rep;
+        let corner_classidx = &sy.corner_classidx;
+        let corner_sym = &sy.corner_sym;
+        let corner_rep = &sy.corner_rep;
 
         self.twist = twist_move[N_MOVE * self.twist as usize + m as usize];
         self.flip = flip_move[N_MOVE * self.flip as usize + m as usize];
@@ -146,16 +150,22 @@ impl CoordCube {
         self.corner_classidx = corner_classidx[self.corners as usize];
         self.corner_sym = corner_sym[self.corners as usize];
         self.corner_rep = corner_rep[self.corner_classidx as usize];
-        Ok(())
+    }
+
+    /// Update phase 1 coordinates when a sequence of moves is apply.
+    pub fn phase1_moves(&mut self, moves: &[moves::Move], mv: &MoveTables, sy: &SymmetriesTables) {
+        for m in moves {
+            self.phase1_move(*m, mv, sy);
+        }
     }
 
     /// Update phase 2 coordinates when move is apply.
     /// 
     /// :param m: The move
-    pub fn phase2_move(&mut self, m: moves::Move) -> Result<(), Error>{
-        let slice_sorted_move = moves::move_slice_sorted()?;
-        let corners_move = moves::move_corners()?;
-        let ud_edges_move = moves::move_ud_edges()?;
+    pub fn phase2_move(&mut self, m: moves::Move, mv: &MoveTables) {
+        let slice_sorted_move = &mv.slice_sorted_move;
+        let corners_move = &mv.corners_move;
+        let ud_edges_move = &mv.ud_edges_move;
 
         self.slice_sorted = slice_sorted_move[N_MOVE * self.slice_sorted as usize + m as usize];
         self.corners = corners_move[N_MOVE * self.corners as usize + m as usize];
@@ -164,7 +174,13 @@ impl CoordCube {
             65535 => ud_edges_move[N_UD_EDGES * N_MOVE + m as usize - N_MOVE],
             _ => ud_edges_move[N_MOVE * self.ud_edges as usize + m as usize],
         };
-        Ok(())
+    }
+
+    /// Update phase 2 coordinates when a sequence of phase 2 moves is apply.
+    pub fn phase2_moves(&mut self, moves: &[moves::Move], mv: &MoveTables) {
+        for m in moves {
+            self.phase2_move(*m, mv);
+        }
     }
 
 }
@@ -260,6 +276,7 @@ mod test {
     #[test]
     fn test_coordcube() {
         let sy = SymmetriesTables::new();
+        let mv = MoveTables::new();
         let fc =
             FaceCube::try_from("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF").unwrap();
         let cc = CubieCube::try_from(&fc).unwrap();
@@ -277,7 +294,7 @@ mod test {
         assert_eq!(cdc.corner_classidx, 716);
         assert_eq!(cdc.corner_sym, 7);
         assert_eq!(cdc.corner_rep, 1260);
-        let _ = cdc.phase1_move(Move::U2);
+        cdc.phase1_move(Move::U2, &mv, &sy);
         assert_eq!(cdc.twist, 1229);
         assert_eq!(cdc.flip, 1898);
         assert_eq!(cdc.slice_sorted, 5061);
@@ -291,7 +308,7 @@ mod test {
         assert_eq!(cdc.corner_classidx, 1321);
         assert_eq!(cdc.corner_sym, 7);
         assert_eq!(cdc.corner_rep, 2459);
-        let _ = cdc.phase2_move(Move::R2);
+        cdc.phase2_move(Move::R2, &mv);
         assert_eq!(cdc.twist, 1229);
         assert_eq!(cdc.flip, 1898);
         assert_eq!(cdc.slice_sorted, 5116);
@@ -307,6 +324,34 @@ mod test {
         assert_eq!(cdc.corner_rep, 2459);
     }
     
+    #[test]
+    fn test_move_sequence() {
+        let sy = SymmetriesTables::new();
+        let mv = MoveTables::new();
+        let fc =
+            FaceCube::try_from("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF").unwrap();
+        let cc = CubieCube::try_from(&fc).unwrap();
+        let moves = [Move::R, Move::U3, Move::F2, Move::L, Move::B3, Move::D];
+        let mut cdc = CoordCube::from_cubie(&cc, &sy).unwrap();
+        cdc.phase1_moves(&moves, &mv, &sy);
+        let expected = CoordCube::from_cubie(&cc.apply_moves(&moves), &sy).unwrap();
+        assert_eq!(cdc.twist, expected.twist);
+        assert_eq!(cdc.flip, expected.flip);
+        assert_eq!(cdc.slice_sorted, expected.slice_sorted);
+        assert_eq!(cdc.corners, expected.corners);
+        assert_eq!(cdc.flipslice_classidx, expected.flipslice_classidx);
+        assert_eq!(cdc.corner_classidx, expected.corner_classidx);
+
+        let moves = [Move::U, Move::R2, Move::D3, Move::F2];
+        let cc = CubieCube::default().apply_moves(&[Move::L2, Move::U3, Move::B2]);
+        let mut cdc = CoordCube::from_cubie(&cc, &sy).unwrap();
+        cdc.phase2_moves(&moves, &mv);
+        let expected = CoordCube::from_cubie(&cc.apply_moves(&moves), &sy).unwrap();
+        assert_eq!(cdc.corners, expected.corners);
+        assert_eq!(cdc.ud_edges, expected.ud_edges);
+        assert_eq!(cdc.slice_sorted, expected.slice_sorted);
+    }
+
     #[test]
     fn test_create_phase2_edgemerge_table() {
         let ud_edges = create_phase2_edgemerge_table().unwrap();
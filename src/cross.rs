@@ -0,0 +1,374 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::*;
+use crate::cubie::{move_cubes, Corner, Corner::*, CubieCube, Edge, Edge::*};
+use crate::error::Error;
+use crate::facelet::{Color, EDGE_FACELET};
+use crate::moves::{self, Move};
+use crate::symmetries::{self, conjugate, conjugate_move};
+use crate::{create_tables_dir, decode_table, write_table};
+
+/// Number of orientations of the four cross edges.
+const N_CROSS_FLIP: usize = 16;
+/// Number of cross states: the d_edges coordinate and the orientations of the DR, DF, DL and DB edges.
+const N_CROSS: usize = N_SLICE_SORTED * N_CROSS_FLIP;
+/// Number of states of a corner and an edge: 8 * 3 corner locations and 12 * 2 edge locations.
+const N_PAIR: usize = 24 * 24;
+
+/// The F2L slots next to the D cross: the corner and the middle layer edge.
+pub const D_SLOTS: [(Corner, Edge); 4] = [(DFR, FR), (DLF, FL), (DBL, BL), (DRB, BR)];
+
+lazy_static! {
+    /// The tables of the cross solver, created/loaded on first use.
+    static ref CROSS_TABLES: CrossTables = CrossTables::new().unwrap();
+}
+
+/// Move and distance tables for the cross on the D face.
+struct CrossTables {
+    d_edges_move: Vec<u16>,
+    /// The orientations of the cross edges which change by a move, for each d_edges coordinate.
+    flip_mask: Vec<u8>,
+    pair_move: Vec<u16>,
+    distance: Vec<u8>,
+    /// The distance tables of the pairs of the [D_SLOTS].
+    pair_distance: Vec<Vec<u8>>,
+}
+
+impl CrossTables {
+    fn new() -> Result<Self, Error> {
+        let mut tables = Self {
+            d_edges_move: moves::move_d_edges()?,
+            flip_mask: cross_flip_mask(),
+            pair_move: pair_move(),
+            distance: Vec::new(),
+            pair_distance: Vec::new(),
+        };
+        tables.distance = tables.cross_distance()?;
+        tables.pair_distance = D_SLOTS
+            .iter()
+            .map(|slot| tables.slot_distance(*slot))
+            .collect();
+        Ok(tables)
+    }
+
+    fn cross_move(&self, idx: usize, m: usize) -> usize {
+        let (d_edges, flip) = (idx / N_CROSS_FLIP, idx % N_CROSS_FLIP);
+        let i = N_MOVE * d_edges + m;
+        self.d_edges_move[i] as usize * N_CROSS_FLIP + (flip ^ self.flip_mask[i] as usize)
+    }
+
+    /// Distance table for the D cross, created by a breadth-first search. The maximal distance is 8 moves.
+    fn cross_distance(&self) -> Result<Vec<u8>, Error> {
+        create_tables_dir()?;
+        let fname = "tables/cross_distance";
+        let cross_table = std::fs::read(fname).unwrap_or("".into());
+        if !cross_table.is_empty() {
+            return decode_table(&cross_table);
+        }
+        println!("Creating {} table...", fname);
+        let distance = breadth_first(N_CROSS, 0, |idx, m| self.cross_move(idx, m));
+        write_table(fname, &distance)?;
+        Ok(distance)
+    }
+
+    /// Distance table for a corner and an edge to the slot.
+    fn slot_distance(&self, slot: (Corner, Edge)) -> Vec<u8> {
+        let solved = pair_index((slot.0, 0), (slot.1, 0));
+        breadth_first(N_PAIR, solved, |idx, m| {
+            self.pair_move[N_MOVE * idx + m] as usize
+        })
+    }
+
+    /// Collect the maneuvers with togo moves which solve the cross and the pair, the pair distances are
+    /// None for the cross alone.
+    fn search(
+        &self,
+        (cross, pair): (usize, usize),
+        pair_distance: Option<&[u8]>,
+        togo: usize,
+        maneuver: &mut Vec<Move>,
+        solutions: &mut Vec<Vec<Move>>,
+    ) {
+        if togo == 0 {
+            solutions.push(maneuver.clone());
+            return;
+        }
+        for m in ALL_MOVES {
+            if let Some(last) = maneuver.last() {
+                // successive moves on opposite faces only in one order
+                if [0, 3].contains(&(*last as i8 / 3 - m as i8 / 3)) {
+                    continue;
+                }
+            }
+            let cross1 = self.cross_move(cross, m as usize);
+            let pair1 = self.pair_move[N_MOVE * pair + m as usize] as usize;
+            let dist = pair_distance
+                .map_or(0, |d| d[pair1])
+                .max(self.distance[cross1]);
+            if (dist as usize) < togo {
+                maneuver.push(m);
+                self.search(
+                    (cross1, pair1),
+                    pair_distance,
+                    togo - 1,
+                    maneuver,
+                    solutions,
+                );
+                maneuver.pop();
+            }
+        }
+    }
+}
+
+/// Distances from the state solved for all states, for n states with the transition function moved.
+fn breadth_first(n: usize, solved: usize, moved: impl Fn(usize, usize) -> usize) -> Vec<u8> {
+    let mut distance = vec![u8::MAX; n];
+    distance[solved] = 0;
+    let mut frontier = vec![solved];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        let mut next = Vec::new();
+        for idx in frontier {
+            for m in 0..N_MOVE {
+                let idx1 = moved(idx, m);
+                if distance[idx1] == u8::MAX {
+                    distance[idx1] = depth + 1;
+                    next.push(idx1);
+                }
+            }
+        }
+        frontier = next;
+        depth += 1;
+    }
+    distance
+}
+
+/// For each d_edges coordinate and move the mask of the cross edges DR, DF, DL, DB which are flipped.
+fn cross_flip_mask() -> Vec<u8> {
+    let mc = move_cubes();
+    let mut cc = CubieCube::default();
+    let mut flip_mask = vec![0; N_SLICE_SORTED * N_MOVE];
+    for d_edges in 0..N_SLICE_SORTED {
+        cc.set_d_edges(d_edges as u16);
+        for m in 0..N_MOVE {
+            for (q, p) in mc[m].ep.iter().enumerate() {
+                // the piece at position p moves to position q
+                let piece = cc.ep[*p as usize];
+                if (DR..=DB).contains(&piece) && mc[m].eo[q] == 1 {
+                    flip_mask[N_MOVE * d_edges + m] |= 1 << (piece as usize - DR as usize);
+                }
+            }
+        }
+    }
+    flip_mask
+}
+
+fn pair_index(corner: (Corner, u8), edge: (Edge, u8)) -> usize {
+    24 * (3 * corner.0 as usize + corner.1 as usize) + 2 * edge.0 as usize + edge.1 as usize
+}
+
+/// Move table for the location of a corner and an edge.
+fn pair_move() -> Vec<u16> {
+    let mc = move_cubes();
+    let mut pair_move = vec![0; N_PAIR * N_MOVE];
+    for (c, co) in ALL_CORNERS
+        .iter()
+        .flat_map(|c| (0..3).map(move |o| (*c, o)))
+    {
+        for (e, eo) in ALL_EDGES.iter().flat_map(|e| (0..2).map(move |o| (*e, o))) {
+            for m in 0..N_MOVE {
+                let q = mc[m].cp.iter().position(|p| *p == c).unwrap();
+                let r = mc[m].ep.iter().position(|p| *p == e).unwrap();
+                pair_move[N_MOVE * pair_index((c, co), (e, eo)) + m] = pair_index(
+                    (ALL_CORNERS[q], (co + mc[m].co[q]) % 3),
+                    (ALL_EDGES[r], (eo + mc[m].eo[r]) % 2),
+                ) as u16;
+            }
+        }
+    }
+    pair_move
+}
+
+/// The cross index of a cube: the d_edges coordinate and the orientations of the DR, DF, DL and DB edges.
+fn cross_index(cc: &CubieCube) -> usize {
+    let mut flip = 0;
+    for (p, piece) in cc.ep.iter().enumerate() {
+        if (DR..=DB).contains(piece) {
+            flip |= (cc.eo[p] as usize) << (*piece as usize - DR as usize);
+        }
+    }
+    cc.get_d_edges() as usize * N_CROSS_FLIP + flip
+}
+
+/// The rotation which maps the face to D by conjugation: s^-1 * face * s = D.
+pub(crate) fn rotation_to_d(face: Color) -> CubieCube {
+    let mc = move_cubes();
+    symmetries::sc()
+        .into_iter()
+        .step_by(2) // the rotations, the odd ones are reflections
+        .find(|s| conjugate(s, &mc[3 * face as usize]) == mc[Move::D as usize])
+        .unwrap()
+}
+
+/// Check if the four edges of face are in place and oriented.
+pub fn is_cross_solved(cc: &CubieCube, face: Color) -> bool {
+    EDGE_FACELET.iter().enumerate().all(|(i, facelets)| {
+        !facelets.iter().any(|f| *f as usize / 9 == face as usize)
+            || (cc.ep[i] as usize == i && cc.eo[i] == 0)
+    })
+}
+
+/// All optimal solutions of the cross on one face.
+///
+/// * `face`: The face of the cross, given by its center color.
+/// * `length`: The number of moves of the optimal solutions.
+/// * `solutions`: All optimal solutions, successive moves on opposite faces are given in one order only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrossSolutions {
+    pub face: Color,
+    pub length: usize,
+    pub solutions: Vec<Vec<Move>>,
+}
+
+/// Give all optimal solutions of the cross on face, or of the X-cross (the cross and one F2L pair in any of the four
+/// slots) if xcross is true.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cross::solve_cross;
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::facelet::Color;
+/// use kociemba::scramble::scramble_from_str;
+///
+/// fn main() {
+///     let scramble = scramble_from_str("R U R' U' F2 D L2 B' R2 U").unwrap();
+///     let cc = CubieCube::from(&scramble);
+///     let crosses = solve_cross(&cc, Color::D, false).unwrap();
+///     println!("{} moves: {:?}", crosses.length, crosses.solutions);
+/// }
+/// ```
+pub fn solve_cross(cc: &CubieCube, face: Color, xcross: bool) -> Result<CrossSolutions, Error> {
+    cc.verify()?;
+    let tables = &*CROSS_TABLES;
+    let s = rotation_to_d(face);
+    let cd = conjugate(&s, cc);
+    let cross = cross_index(&cd);
+
+    let mut solutions = Vec::new();
+    if xcross {
+        let slots: Vec<_> = D_SLOTS
+            .iter()
+            .zip(&tables.pair_distance)
+            .map(|(slot, pair_distance)| {
+                let pair = pair_index(cd.corner_location(slot.0), cd.edge_location(slot.1));
+                (pair, pair_distance)
+            })
+            .collect();
+        let mut togo = tables.distance[cross] as usize;
+        while solutions.is_empty() {
+            for (pair, pair_distance) in &slots {
+                if (pair_distance[*pair] as usize) <= togo {
+                    let mut found = Vec::new();
+                    tables.search(
+                        (cross, *pair),
+                        Some(pair_distance.as_slice()),
+                        togo,
+                        &mut Vec::new(),
+                        &mut found,
+                    );
+                    // a maneuver may solve two slots
+                    found.retain(|f| !solutions.contains(f));
+                    solutions.extend(found);
+                }
+            }
+            togo += 1;
+        }
+    } else {
+        let togo = tables.distance[cross] as usize;
+        tables.search((cross, 0), None, togo, &mut Vec::new(), &mut solutions);
+    }
+
+    // a move m of the rotated cube is the move s * m * s^-1 of the cube
+    let s_inv = s.inverse_cubie_cube();
+    let solutions: Vec<Vec<Move>> = solutions
+        .into_iter()
+        .map(|sol| sol.into_iter().map(|m| conjugate_move(&s_inv, m)).collect())
+        .collect();
+    Ok(CrossSolutions {
+        face,
+        length: solutions[0].len(),
+        solutions,
+    })
+}
+
+/// Give all optimal solutions of the cross (or X-cross) for each of the six faces.
+pub fn solve_all_crosses(cc: &CubieCube, xcross: bool) -> Result<Vec<CrossSolutions>, Error> {
+    ALL_COLORS
+        .iter()
+        .map(|face| solve_cross(cc, *face, xcross))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cross::*;
+    use crate::moves::Move::*;
+    use crate::scramble::scramble_from_str;
+
+    #[test]
+    fn test_cross_distance() {
+        let mut count = [0; 9];
+        for d in CROSS_TABLES.distance.iter() {
+            count[*d as usize] += 1;
+        }
+        assert_eq!(count, [1, 15, 158, 1394, 9809, 46381, 97254, 34966, 102]);
+    }
+
+    #[test]
+    fn test_solve_cross() {
+        let cc = CubieCube::from(&vec![R, U, F3]);
+        let crosses = solve_cross(&cc, Color::D, false).unwrap();
+        // U does not move the D cross edges
+        assert_eq!(crosses.length, 2);
+        assert!(crosses.solutions.contains(&vec![F, R3]));
+        // F and B commute, the solution is given in one order only
+        let crosses = solve_cross(&CubieCube::from(&vec![F, B]), Color::D, false).unwrap();
+        assert_eq!(crosses.solutions, vec![vec![F3, B3]]);
+
+        let scramble =
+            scramble_from_str("D2 F2 U' B2 R2 B2 R2 L B' D' F D2 F' R U2 L' U' B L2").unwrap();
+        let cc = CubieCube::from(&scramble);
+        for crosses in solve_all_crosses(&cc, false).unwrap() {
+            assert!(crosses.length <= 8);
+            for s in &crosses.solutions {
+                assert_eq!(s.len(), crosses.length);
+                assert!(is_cross_solved(&cc.apply_moves(s), crosses.face));
+                for i in 1..s.len() {
+                    if s[i - 1].is_inverse(s[i]) {
+                        let mut swapped = s.clone();
+                        swapped.swap(i - 1, i);
+                        assert!(!crosses.solutions.contains(&swapped));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_xcross() {
+        let scramble =
+            scramble_from_str("D2 F2 U' B2 R2 B2 R2 L B' D' F D2 F' R U2 L' U' B L2").unwrap();
+        let cc = CubieCube::from(&scramble);
+        let cross = solve_cross(&cc, Color::U, false).unwrap();
+        let xcross = solve_cross(&cc, Color::U, true).unwrap();
+        assert!(xcross.length >= cross.length);
+        for s in &xcross.solutions {
+            let solved = cc.apply_moves(s);
+            assert!(is_cross_solved(&solved, Color::U));
+            let rotated = conjugate(&rotation_to_d(Color::U), &solved);
+            assert!(D_SLOTS.iter().any(|(c, e)| {
+                rotated.corner_location(*c) == (*c, 0) && rotated.edge_location(*e) == (*e, 0)
+            }));
+        }
+    }
+}
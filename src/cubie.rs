@@ -1,4 +1,5 @@
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops::Mul;
 
@@ -11,9 +12,11 @@ use crate::{facelet::*, moves::*};
 /// Represents the 8 corners on the cube, described by the layer they are on.
 /// 
 /// Example: `ULB` (Up, Left, Bottom).
+///
+/// Serialized by name, e.g. `"ULB"`.
 #[rustfmt::skip]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
 pub enum Corner {
     URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB,
 }
@@ -45,9 +48,11 @@ impl TryFrom<u8> for Corner {
 /// Represents the 12 edges on the cube, described by the layer they are on.
 /// 
 /// Example: `BL` (Bottom, Left).
+///
+/// Serialized by name, e.g. `"BL"`.
 #[rustfmt::skip]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
 pub enum Edge {
     UR, UF, UL, UB, DR, DF, DL, DB, FR, FL, BL, BR,
 }
@@ -81,7 +86,15 @@ impl TryFrom<u8> for Edge {
 }
 
 /// Cube on the cubie level.
-#[derive(Debug, PartialEq, Clone, Copy)]
+///
+/// Serialized as the four cubie arrays, e.g. in JSON the solved cube is
+/// ```text
+/// {"cp":["URF","UFL","ULB","UBR","DFR","DLF","DBL","DRB"],"co":[0,0,0,0,0,0,0,0],
+///  "ep":["UR","UF","UL","UB","DR","DF","DL","DB","FR","FL","BL","BR"],"eo":[0,0,0,0,0,0,0,0,0,0,0,0]}
+/// ```
+/// Deserialization does not check the cube, use [CubieCube::verify] on untrusted input.
+/// See [CubieCube::to_bytes] for a compact binary encoding.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct CubieCube {
     /// Corner permutation, relative to SOLVED_STATE.
     pub cp: [Corner; 8],
@@ -152,6 +165,27 @@ impl From<&Vec<Move>> for CubieCube {
     }
 }
 
+/// Decode a cube from the compact encoding produced by [CubieCube::to_bytes].
+impl TryFrom<&[u8]> for CubieCube {
+    type Error = Error;
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != 20 {
+            return Err(Error::InvalidCubieValue);
+        }
+        let mut state = CubieCube::default();
+        for (i, b) in bytes[..8].iter().enumerate() {
+            state.cp[i] = Corner::try_from(b / 3)?;
+            state.co[i] = b % 3;
+        }
+        for (i, b) in bytes[8..].iter().enumerate() {
+            state.ep[i] = Edge::try_from(b / 2)?;
+            state.eo[i] = b % 2;
+        }
+        state.verify()?;
+        Ok(state)
+    }
+}
+
 /// Gives cubie representation of a face cube (facelet).
 impl TryFrom<&FaceCube> for CubieCube {
     type Error = Error;
@@ -843,8 +877,14 @@ impl CubieCube {
 
     /// Generate a random cube. The probability is the same for all possible states.
     pub fn randomize(&mut self) {
+        self.randomize_with(&mut thread_rng());
+    }
+
+    /// Generate a random cube with the random number generator rng, e.g. a seeded `StdRng` for
+    /// reproducible cubes. The probability is the same for all possible states.
+    pub fn randomize_with<R: Rng>(&mut self, rng: &mut R) {
         // The permutation of the 12 edges. 0 <= idx < 12!."""
-        let mut idx = random::<usize>() % 479001600; // 12!
+        let mut idx = rng.gen::<usize>() % 479001600; // 12!
         self.cp = ALL_CORNERS.clone();
         for j in ALL_EDGES {
             let mut k = idx % (j as usize + 1);
@@ -856,14 +896,14 @@ impl CubieCube {
         }
         let p = self.edge_parity();
         loop {
-            self.set_corners(random::<u16>() % 40320); // 8!
+            self.set_corners(rng.gen::<u16>() % 40320); // 8!
             if p == self.corner_parity() {
                 // parities of edge and corner permutations must be the same
                 break;
             }
         }
-        self.set_flip(random::<u16>() % 2048); // 2^11
-        self.set_twist(random::<u16>() % 2187); // 3^7
+        self.set_flip(rng.gen::<u16>() % 2048); // 2^11
+        self.set_twist(rng.gen::<u16>() % 2187); // 3^7
     }
 
     /// Check if cubiecube is valid.
@@ -907,6 +947,188 @@ impl CubieCube {
         }
         Ok(true)
     }
+
+    /// Encode the cube in 20 bytes: one byte `3 * cp + co` per corner position followed by one
+    /// byte `2 * ep + eo` per edge position.
+    ///
+    /// Decode with `CubieCube::try_from(&bytes[..])`.
+    pub fn to_bytes(&self) -> [u8; 20] {
+        let mut bytes = [0; 20];
+        for (i, b) in bytes[..8].iter_mut().enumerate() {
+            *b = 3 * self.cp[i] as u8 + self.co[i];
+        }
+        for (i, b) in bytes[8..].iter_mut().enumerate() {
+            *b = 2 * self.ep[i] as u8 + self.eo[i];
+        }
+        bytes
+    }
+
+    /// Give the position and orientation of corner cubie `c` in this cube.
+    ///
+    /// Applied to the cube of a maneuver this tells where the piece ends up after the maneuver.
+    pub fn corner_location(&self, c: Corner) -> (Corner, u8) {
+        let i = self.cp.iter().position(|&p| p == c).unwrap();
+        (ALL_CORNERS[i], self.co[i])
+    }
+
+    /// Give the position and orientation of edge cubie `e` in this cube.
+    ///
+    /// Applied to the cube of a maneuver this tells where the piece ends up after the maneuver.
+    pub fn edge_location(&self, e: Edge) -> (Edge, u8) {
+        let i = self.ep.iter().position(|&p| p == e).unwrap();
+        (ALL_EDGES[i], self.eo[i])
+    }
+
+    /// Decompose the corner permutation into disjoint cycles.
+    ///
+    /// Every cycle lists the positions in the order the pieces travel: the piece at `corners[0]` moves
+    /// to `corners[1]` and so on. Corners which are solved are omitted, corners which are only twisted
+    /// in place give a cycle of length 1.
+    pub fn corner_cycles(&self) -> Vec<CornerCycle> {
+        let inv = self.inverse_cubie_cube();
+        let mut visited = [false; 8];
+        let mut cycles = Vec::new();
+        for c in ALL_CORNERS {
+            if visited[c as usize] {
+                continue;
+            }
+            let mut corners = Vec::new();
+            let mut twist = 0;
+            let mut i = c;
+            while !visited[i as usize] {
+                visited[i as usize] = true;
+                corners.push(i);
+                twist += self.co[i as usize];
+                i = inv.cp[i as usize];
+            }
+            let twist = twist % 3;
+            if corners.len() > 1 || twist != 0 {
+                cycles.push(CornerCycle { corners, twist });
+            }
+        }
+        cycles
+    }
+
+    /// Decompose the edge permutation into disjoint cycles.
+    ///
+    /// Every cycle lists the positions in the order the pieces travel: the piece at `edges[0]` moves
+    /// to `edges[1]` and so on. Edges which are solved are omitted, edges which are only flipped
+    /// in place give a cycle of length 1.
+    pub fn edge_cycles(&self) -> Vec<EdgeCycle> {
+        let inv = self.inverse_cubie_cube();
+        let mut visited = [false; 12];
+        let mut cycles = Vec::new();
+        for e in ALL_EDGES {
+            if visited[e as usize] {
+                continue;
+            }
+            let mut edges = Vec::new();
+            let mut flip = 0;
+            let mut i = e;
+            while !visited[i as usize] {
+                visited[i as usize] = true;
+                edges.push(i);
+                flip += self.eo[i as usize];
+                i = inv.ep[i as usize];
+            }
+            let flip = flip % 2;
+            if edges.len() > 1 || flip != 0 {
+                cycles.push(EdgeCycle { edges, flip });
+            }
+        }
+        cycles
+    }
+
+    /// Give the cycle structure of the cube in cycle notation, corner cycles first.
+    ///
+    /// Example: `(URF ULB DRB)+ (UF UR UB)`, the empty string for the solved cube.
+    pub fn cycle_notation(&self) -> String {
+        let corners = self.corner_cycles().into_iter().map(|c| c.to_string());
+        let edges = self.edge_cycles().into_iter().map(|e| e.to_string());
+        corners.chain(edges).collect::<Vec<String>>().join(" ")
+    }
+
+    /// Give the order of the cube, i.e. the number of times the maneuver which produces this cube
+    /// has to be repeated to return to the solved cube.
+    ///
+    /// A twisted corner cycle has to be traversed three times, a flipped edge cycle twice.
+    pub fn order(&self) -> usize {
+        let corners = self
+            .corner_cycles()
+            .into_iter()
+            .map(|c| c.len() * if c.twist == 0 { 1 } else { 3 });
+        let edges = self
+            .edge_cycles()
+            .into_iter()
+            .map(|e| e.len() * if e.flip == 0 { 1 } else { 2 });
+        corners.chain(edges).fold(1, lcm)
+    }
+}
+
+/// A cycle of corner positions, see [CubieCube::corner_cycles].
+///
+/// `twist` is the total twist the pieces pick up while traversing the cycle once: 0, 1 (clockwise)
+/// or 2 (counter-clockwise).
+#[derive(Debug, PartialEq, Clone)]
+pub struct CornerCycle {
+    pub corners: Vec<Corner>,
+    pub twist: u8,
+}
+
+impl CornerCycle {
+    /// Number of corners in the cycle.
+    pub fn len(&self) -> usize {
+        self.corners.len()
+    }
+
+    /// Always false, empty cycles are never produced.
+    pub fn is_empty(&self) -> bool {
+        self.corners.is_empty()
+    }
+}
+
+impl fmt::Display for CornerCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = self.corners.iter().map(|c| c.to_string()).collect();
+        let suffix = match self.twist {
+            1 => "+",
+            2 => "-",
+            _ => "",
+        };
+        write!(f, "({}){}", names.join(" "), suffix)
+    }
+}
+
+/// A cycle of edge positions, see [CubieCube::edge_cycles].
+///
+/// `flip` is the total flip the pieces pick up while traversing the cycle once: 0 or 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EdgeCycle {
+    pub edges: Vec<Edge>,
+    pub flip: u8,
+}
+
+impl EdgeCycle {
+    /// Number of edges in the cycle.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Always false, empty cycles are never produced.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+impl fmt::Display for EdgeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = self.edges.iter().map(|e| e.to_string()).collect();
+        let suffix = match self.flip {
+            1 => "+",
+            _ => "",
+        };
+        write!(f, "({}){}", names.join(" "), suffix)
+    }
 }
 
 /// these cubes represent the basic cube moves.
@@ -992,6 +1214,15 @@ pub fn c_nk(n: u32, k: u32) -> u32 {
     s
 }
 
+/// Least common multiple of a and b.
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
+
 #[cfg(test)]
 mod test {
     use crate::cubie::*;
@@ -1308,4 +1539,78 @@ mod test {
         assert_eq!(state.count_corner_twist(), 3);
         assert_eq!(state.count_edge_twist(), 2);
     }
+
+    #[test]
+    fn test_cycles() {
+        let state = CubieCube::default();
+        assert_eq!(state.corner_cycles(), vec![]);
+        assert_eq!(state.edge_cycles(), vec![]);
+        assert_eq!(state.cycle_notation(), "");
+
+        let state = CubieCube::default().apply_move(U);
+        assert_eq!(
+            state.corner_cycles(),
+            vec![CornerCycle {
+                corners: vec![URF, UFL, ULB, UBR],
+                twist: 0
+            }]
+        );
+        assert_eq!(state.cycle_notation(), "(URF UFL ULB UBR) (UR UF UL UB)");
+
+        let state = CubieCube::from(&vec![R, U, R3, U3]);
+        assert_eq!(state.cycle_notation(), "(URF DFR)+ (ULB UBR)- (UR UB FR)");
+    }
+
+    #[test]
+    fn test_order() {
+        assert_eq!(CubieCube::default().order(), 1);
+        assert_eq!(CubieCube::default().apply_move(R2).order(), 2);
+        assert_eq!(CubieCube::from(&vec![R, U, R3, U3]).order(), 6);
+        assert_eq!(CubieCube::from(&vec![R, U]).order(), 105);
+
+        let state = CubieCube::from(&vec![R, U2, D3, B, D3]);
+        assert_eq!(state.order(), 1260);
+        let mut cc = CubieCube::default();
+        for _ in 0..1260 {
+            cc = cc * state;
+        }
+        assert_eq!(cc, SOLVED_CUBIE_CUBE);
+    }
+
+    #[test]
+    fn test_location() {
+        let state = CubieCube::default().apply_move(R);
+        assert_eq!(state.corner_location(URF), (UBR, 1));
+        assert_eq!(state.corner_location(DFR), (URF, 2));
+        assert_eq!(state.edge_location(UR), (BR, 0));
+        assert_eq!(state.edge_location(FL), (FL, 0));
+    }
+
+    #[test]
+    fn test_bytes() {
+        let state = CubieCube::from(&vec![R, U, R3, U3, R3, F, R, F3]);
+        let bytes = state.to_bytes();
+        assert_eq!(CubieCube::try_from(&bytes[..]).unwrap(), state);
+        assert_eq!(
+            CubieCube::default().to_bytes(),
+            [0, 3, 6, 9, 12, 15, 18, 21, 0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22]
+        );
+        assert!(CubieCube::try_from(&bytes[1..]).is_err());
+        let mut bytes = CubieCube::default().to_bytes();
+        bytes[0] = 1; // twisted corner
+        assert!(CubieCube::try_from(&bytes[..]).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&CubieCube::default()).unwrap();
+        assert_eq!(
+            json,
+            "{\"cp\":[\"URF\",\"UFL\",\"ULB\",\"UBR\",\"DFR\",\"DLF\",\"DBL\",\"DRB\"],\"co\":[0,0,0,0,0,0,0,0],\
+             \"ep\":[\"UR\",\"UF\",\"UL\",\"UB\",\"DR\",\"DF\",\"DL\",\"DB\",\"FR\",\"FL\",\"BL\",\"BR\"],\"eo\":[0,0,0,0,0,0,0,0,0,0,0,0]}"
+        );
+        let state = CubieCube::from(&vec![R, U, R3, U3]);
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<CubieCube>(&json).unwrap(), state);
+    }
 }
@@ -0,0 +1,250 @@
+use std::mem;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::moves::Move;
+use crate::solver::{self, SearchLimits, SharedSearch};
+
+/// Options of [enumerate_solutions].
+///
+/// * `max_solutions`: Give at most this many solutions, the shortest ones. None for all solutions.
+/// * `first_move`: Only accept solutions which can start with this move.
+/// * `last_move`: Only accept solutions which can end with this move.
+///
+/// Moves on opposite faces commute, so e.g. `U D R` can start with U and with D.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnumerateOptions {
+    pub max_solutions: Option<usize>,
+    pub first_move: Option<Move>,
+    pub last_move: Option<Move>,
+}
+
+impl EnumerateOptions {
+    /// Check the first and last move of the normalized maneuver.
+    pub fn accepts(&self, maneuver: &[Move]) -> bool {
+        let first_ok = self.first_move.is_none_or(|m| {
+            maneuver
+                .iter()
+                .take_while(|n| axis(**n) == axis(maneuver[0]))
+                .any(|n| *n == m)
+        });
+        let last_ok = self.last_move.is_none_or(|m| {
+            maneuver
+                .iter()
+                .rev()
+                .take_while(|n| axis(**n) == axis(maneuver[maneuver.len() - 1]))
+                .any(|n| *n == m)
+        });
+        first_ok && last_ok
+    }
+}
+
+/// Axis of the face of the move m: 0 for U and D, 1 for R and L, 2 for F and B.
+fn axis(m: Move) -> u8 {
+    (m as u8 / 3) % 3
+}
+
+/// Give the normal form of a maneuver modulo commutation: successive moves on opposite faces are ordered
+/// U before D, R before L and F before B.
+pub fn normalize(maneuver: &[Move]) -> Vec<Move> {
+    let mut normal = maneuver.to_vec();
+    for run in normal.chunk_by_mut(|a, b| axis(*a) == axis(*b)) {
+        run.sort_by_key(|m| *m as u8);
+    }
+    normal
+}
+
+/// Enumerate distinct solutions which transform the cube start into the cube goal.
+///
+/// All solutions with at most `limits.max_length` moves are collected, length by length, so with
+/// `options.max_solutions` the shortest ones are given. Unlike the two-phase solver the search is not restricted by
+/// the phase depth caps of limits, so e.g. solutions with only half turns of R, F, L and B are found as well.
+/// Solutions which only differ in the order of commuting moves are given once, in the form of [normalize]. The search
+/// runs on the calling thread like [solver::solver_single_threaded]; the timeout and node budget of limits stop it
+/// early, the solutions found so far are given then. The solutions are sorted by length.
+///
+/// The number of solutions grows fast with the length, use a max_length close to the shortest solution or a budget.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::enumerate::{enumerate_solutions, EnumerateOptions};
+/// use kociemba::scramble::scramble_from_str;
+/// use kociemba::solver::SearchLimits;
+///
+/// fn main() {
+///     let start = CubieCube::from(&scramble_from_str("R U R' U' F2").unwrap());
+///     let limits = SearchLimits {
+///         max_length: 7,
+///         ..Default::default()
+///     };
+///     let options = EnumerateOptions {
+///         max_solutions: Some(10),
+///         ..Default::default()
+///     };
+///     let solutions = enumerate_solutions(&start, &CubieCube::default(), &limits, &options).unwrap();
+///     for s in solutions {
+///         println!("{:?}", s);
+///     }
+/// }
+/// ```
+pub fn enumerate_solutions(
+    start: &CubieCube,
+    goal: &CubieCube,
+    limits: &SearchLimits,
+    options: &EnumerateOptions,
+) -> Result<Vec<Vec<Move>>, Error> {
+    let cc = solver::prepare_cubie(start, goal)?;
+    let shared = Arc::new(SharedSearch {
+        enumerate: Some(options.clone()),
+        ..Default::default()
+    });
+    let mut solverthreads = solver::direction_threads(cc, limits, solver::now(), &shared);
+    for total in 0..=limits.max_length as u16 {
+        for (sth, dist) in solverthreads.iter_mut() {
+            if total >= *dist {
+                sth.search_length(*dist, total);
+            }
+        }
+        let found = shared.solutions.lock().unwrap().len();
+        if shared.terminated.load(Ordering::Relaxed)
+            || options.max_solutions.is_some_and(|k| found >= k)
+        {
+            break;
+        }
+    }
+    let mut solutions = mem::take(&mut *shared.solutions.lock().unwrap());
+    solutions.sort_by_key(|s| (s.len(), s.iter().map(|m| *m as u8).collect::<Vec<_>>()));
+    if let Some(k) = options.max_solutions {
+        solutions.truncate(k);
+    }
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::constants::ALL_MOVES;
+    use crate::enumerate::*;
+    use crate::moves::Move::*;
+    use crate::scramble::scramble_from_str;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(&[D, U, R, L3, F2]), vec![U, D, R, L3, F2]);
+        assert_eq!(normalize(&[B, F2, D3]), vec![F2, B, D3]);
+        let options = EnumerateOptions {
+            first_move: Some(D),
+            last_move: Some(B2),
+            ..Default::default()
+        };
+        assert!(options.accepts(&[U, D, F2, B2]));
+        assert!(!options.accepts(&[U, R, D, B2]));
+        assert!(!options.accepts(&[U, D, B2, R]));
+    }
+
+    /// All canonical maneuvers (no successive moves on the same face, U before D, R before L and F before B) with at
+    /// most max_length moves which transform cc into the identity, by brute force.
+    fn brute_force(cc: &CubieCube, max_length: usize, sofar: &mut Vec<Move>, found: &mut Vec<Vec<Move>>) {
+        if *cc == CubieCube::default() {
+            found.push(sofar.clone());
+        }
+        if sofar.len() == max_length {
+            return;
+        }
+        for m in ALL_MOVES {
+            if sofar
+                .last()
+                .is_some_and(|last| [0, 3].contains(&(*last as i8 / 3 - m as i8 / 3)))
+            {
+                continue;
+            }
+            sofar.push(m);
+            brute_force(&cc.apply_move(m), max_length, sofar, found);
+            sofar.pop();
+        }
+    }
+
+    #[test]
+    fn test_enumerate_all() {
+        let limits = SearchLimits {
+            max_length: 6,
+            ..Default::default()
+        };
+        for scramble in ["R U R' U'", "R2 F2 U", "U D' F2"] {
+            let start = CubieCube::from(&scramble_from_str(scramble).unwrap());
+            let mut expected = Vec::new();
+            brute_force(&start, limits.max_length, &mut Vec::new(), &mut expected);
+            expected.sort_by_key(|s| (s.len(), s.iter().map(|m| *m as u8).collect::<Vec<_>>()));
+            let solutions = enumerate_solutions(
+                &start,
+                &CubieCube::default(),
+                &limits,
+                &EnumerateOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(solutions, expected, "{}", scramble);
+        }
+
+        // the phase depth caps are not used
+        let maneuver = scramble_from_str("R2 U F2 D' L2").unwrap();
+        let start = CubieCube::from(&maneuver);
+        let limits = SearchLimits {
+            max_length: 5,
+            max_phase1_length: 0,
+            max_phase2_length: 1,
+            ..Default::default()
+        };
+        let solutions = enumerate_solutions(
+            &start,
+            &CubieCube::default(),
+            &limits,
+            &EnumerateOptions::default(),
+        )
+        .unwrap();
+        assert!(solutions.contains(&normalize(&crate::moves::invert_moves(&maneuver))));
+    }
+
+    #[test]
+    fn test_enumerate_solutions() {
+        let goal = CubieCube::default();
+        let limits = SearchLimits {
+            max_length: 8,
+            ..Default::default()
+        };
+        let options = EnumerateOptions::default();
+
+        // the commuting moves give one solution of length 2
+        let start = CubieCube::from(&vec![U, D]);
+        let solutions = enumerate_solutions(&start, &goal, &limits, &options).unwrap();
+        assert_eq!(solutions[0], vec![U3, D3]);
+        assert!(solutions[1..].iter().all(|s| s.len() == 8));
+
+        let start = CubieCube::from(&scramble_from_str("R U R' U'").unwrap());
+        let solutions = enumerate_solutions(&start, &goal, &limits, &options).unwrap();
+        assert_eq!(solutions.len(), 3);
+        assert_eq!(solutions[0], vec![U, R, U3, R3]);
+        for (i, s) in solutions.iter().enumerate() {
+            assert!(s.len() <= 8);
+            assert_eq!(start.apply_moves(s), goal);
+            assert_eq!(normalize(s), *s);
+            assert!(!solutions[..i].contains(s));
+        }
+
+        let options = EnumerateOptions {
+            max_solutions: Some(1),
+            last_move: Some(R3),
+            ..Default::default()
+        };
+        let filtered = enumerate_solutions(&start, &goal, &limits, &options).unwrap();
+        assert_eq!(filtered, vec![vec![U, R, U3, R3]]);
+        let options = EnumerateOptions {
+            first_move: Some(U),
+            last_move: Some(U2),
+            ..Default::default()
+        };
+        let filtered = enumerate_solutions(&start, &goal, &limits, &options).unwrap();
+        assert_eq!(filtered, vec![vec![U, F3, U2, L3, U3, L, F, U2]]);
+    }
+}
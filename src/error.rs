@@ -19,6 +19,28 @@ pub enum Error {
     InvalidFaceletValue,
     #[error("Invalid cubie reperesentation")]
     InvalidCubieValue,
+    #[error("No solution found within the search limits")]
+    NoSolution,
+    #[error("Invalid start cube: {0}")]
+    InvalidStart(Box<Error>),
+    #[error("Invalid goal cube: {0}")]
+    InvalidGoal(Box<Error>),
+    #[error("Invalid pattern name")]
+    InvalidPattern,
+    #[error("The cube is not in the subgroup H = <U, D, R2, F2, L2, B2>")]
+    NotInSubgroupH,
+    #[error("Invalid center orientation")]
+    InvalidCenterOrientation,
+    #[error("The first two layers are not solved")]
+    F2LNotSolved,
+    #[error("The last layer is not a case of the algorithm set")]
+    InvalidLastLayerCase,
+    #[error("Invalid letter pair")]
+    InvalidLetterPair,
+    #[error("The solution does not transform the start cube into the goal cube")]
+    InvalidSolution,
+    #[error("The search stopped without a result")]
+    SearchFailed,
     #[error("{0}")]
     IOError(#[from] io::Error),
     #[error("{0}")]
@@ -1,10 +1,13 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
 use crate::{cubie::CubieCube, error::Error};
 
 /// Names the colors of the cube facelets: up, right, front, down, left, back.
+///
+/// Serialized by name, e.g. `"U"`.
 #[rustfmt::skip]
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
 pub enum Color {
     U, R, F, D, L, B,
 }
@@ -36,7 +39,9 @@ impl TryFrom<char> for Color {
 /// 
 /// A solved facelet is `UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB`.
 /// 
-#[derive(Debug, PartialEq)]
+/// Serialized as this facelet string, e.g. in JSON `"UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB"`.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FaceCube {
     pub f: [Color; 54],
 }
@@ -115,6 +120,19 @@ impl fmt::Display for FaceCube {
     }
 }
 
+impl Serialize for FaceCube {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for FaceCube {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        FaceCube::try_from(s.as_str()).map_err(de::Error::custom)
+    }
+}
+
 /// The names of the facelet positions of the cube.
 ///
 /// ```text
@@ -256,4 +274,14 @@ mod test {
 
         assert_eq!(face_cube, expected);
     }
+
+    #[test]
+    fn test_serde() {
+        let faces = "DRBLUURLDRBLRRBFLFFUBFFDRUDURRBDFBBULDUDLUDLBUFFDBFLRL";
+        let face_cube = FaceCube::try_from(faces).unwrap();
+        let json = serde_json::to_string(&face_cube).unwrap();
+        assert_eq!(json, format!("\"{}\"", faces));
+        assert_eq!(serde_json::from_str::<FaceCube>(&json).unwrap(), face_cube);
+        assert!(serde_json::from_str::<FaceCube>("\"UUU\"").is_err());
+    }
 }
@@ -0,0 +1,560 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::constants::ALL_MOVES;
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::lastlayer::PLL_ALGORITHMS;
+use crate::moves::{invert_moves, Move};
+use crate::scramble::{scramble_from_str, scramble_to_str};
+use crate::solver::{self, SearchLimits};
+use crate::symmetries::{self, conjugate_move};
+
+lazy_static! {
+    static ref THREE_CYCLES: Vec<Vec<Move>> = three_cycles();
+}
+
+/// Cancel the moves of a maneuver: successive moves on the same face are merged and moves on opposite faces commute,
+/// e.g. `R L R'` gives `L`.
+pub fn cancel_moves(moves: &[Move]) -> Vec<Move> {
+    // runs of moves on one axis with the quarter turns of both faces
+    let mut runs: Vec<(usize, [usize; 2])> = Vec::new();
+    for m in moves {
+        let (face, power) = (*m as usize / 3, *m as usize % 3 + 1);
+        let (axis, side) = (face % 3, face / 3);
+        match runs.last_mut() {
+            Some((a, powers)) if *a == axis => {
+                powers[side] = (powers[side] + power) % 4;
+                if *powers == [0, 0] {
+                    runs.pop();
+                }
+            }
+            _ => {
+                let mut powers = [0, 0];
+                powers[side] = power;
+                runs.push((axis, powers));
+            }
+        }
+    }
+    runs.into_iter()
+        .flat_map(|(axis, powers)| {
+            (0..2)
+                .filter(move |side| powers[*side] != 0)
+                .map(move |side| ALL_MOVES[3 * (axis + 3 * side) + powers[side] - 1])
+        })
+        .collect()
+}
+
+/// The number of moves which cancel when the maneuver b follows the maneuver a.
+pub fn cancellations(a: &[Move], b: &[Move]) -> usize {
+    a.len() + b.len() - cancel_moves(&[a, b].concat()).len()
+}
+
+/// A partial solution found with NISS (normal-inverse scramble switch): moves on the normal scramble and moves on the
+/// inverse scramble.
+///
+/// In notation the moves on the inverse are in parentheses, e.g. `R U (F' D)`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NissSkeleton {
+    pub normal: Vec<Move>,
+    pub inverse: Vec<Move>,
+}
+
+impl TryFrom<&str> for NissSkeleton {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let mut skeleton = NissSkeleton::default();
+        let mut on_inverse = false;
+        for word in s.split_whitespace() {
+            let mut word = word;
+            if let Some(w) = word.strip_prefix('(') {
+                if on_inverse {
+                    return Err(Error::InvalidScramble);
+                }
+                on_inverse = true;
+                word = w;
+            }
+            let closes = word.ends_with(')');
+            word = word.trim_end_matches(')');
+            if !word.is_empty() {
+                let moves = scramble_from_str(word)?;
+                if on_inverse {
+                    skeleton.inverse.extend(moves);
+                } else {
+                    skeleton.normal.extend(moves);
+                }
+            }
+            if closes {
+                if !on_inverse {
+                    return Err(Error::InvalidScramble);
+                }
+                on_inverse = false;
+            }
+        }
+        if on_inverse {
+            return Err(Error::InvalidScramble);
+        }
+        Ok(skeleton)
+    }
+}
+
+impl fmt::Display for NissSkeleton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let normal = scramble_to_str(&self.normal).map_err(|_| fmt::Error)?;
+        let inverse = scramble_to_str(&self.inverse).map_err(|_| fmt::Error)?;
+        match (normal.is_empty(), inverse.is_empty()) {
+            (_, true) => write!(f, "{}", normal),
+            (true, false) => write!(f, "({})", inverse),
+            (false, false) => write!(f, "{} ({})", normal, inverse),
+        }
+    }
+}
+
+impl NissSkeleton {
+    /// The moves on the normal scramble: the normal moves followed by the inverted inverse moves, with cancellations.
+    pub fn linear(&self) -> Vec<Move> {
+        cancel_moves(&[self.normal.clone(), invert_moves(&self.inverse)].concat())
+    }
+
+    /// The cube which is left to solve between the normal and the inverse moves: the inverted inverse moves, the
+    /// scramble and the normal moves.
+    pub fn remaining(&self, scramble: &CubieCube) -> CubieCube {
+        let mut cc = CubieCube::from(&invert_moves(&self.inverse));
+        cc.multiply(*scramble);
+        cc.apply_moves(&self.normal)
+    }
+
+    /// Switch to the inverse scramble: the moves on the normal become the moves on the inverse and vice versa.
+    pub fn switched(&self) -> Self {
+        Self {
+            normal: self.inverse.clone(),
+            inverse: self.normal.clone(),
+        }
+    }
+
+    /// Complete the skeleton into a solution of the scramble.
+    ///
+    /// The [remaining](NissSkeleton::remaining) cube is solved with the two-phase algorithm, the solution is the
+    /// normal moves, this solution and the inverted inverse moves, with cancellations.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use kociemba::cubie::CubieCube;
+    /// use kociemba::fmc::NissSkeleton;
+    /// use kociemba::scramble::scramble_from_str;
+    /// use kociemba::solver::SearchLimits;
+    ///
+    /// fn main() {
+    ///     let scramble = CubieCube::from(&scramble_from_str("R U R' U' F2 D L2 B' R2 U").unwrap());
+    ///     let skeleton = NissSkeleton::try_from("U' R2 (R U' R')").unwrap();
+    ///     let solution = skeleton.complete(&scramble, &SearchLimits::default()).unwrap();
+    ///     assert_eq!(scramble.apply_moves(&solution), CubieCube::default());
+    /// }
+    /// ```
+    pub fn complete(
+        &self,
+        scramble: &CubieCube,
+        limits: &SearchLimits,
+    ) -> Result<Vec<Move>, Error> {
+        let remaining = self.remaining(scramble);
+        let middle = solver::solve_cubie(&remaining, &CubieCube::default(), limits)?.solution;
+        Ok(cancel_moves(
+            &[self.normal.clone(), middle, invert_moves(&self.inverse)].concat(),
+        ))
+    }
+}
+
+/// An insertion of an algorithm into a skeleton.
+///
+/// * `position`: The number of moves of the skeleton before the algorithm.
+/// * `solution`: The skeleton with the inserted algorithm, with cancellations.
+/// * `cancelled`: The number of moves which cancel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Insertion {
+    pub position: usize,
+    pub algorithm: Vec<Move>,
+    pub solution: Vec<Move>,
+    pub cancelled: usize,
+}
+
+/// Check if the cube is a 3-cycle of corners or of edges, the other pieces are solved.
+fn is_three_cycle(cc: &CubieCube) -> bool {
+    let corners = cc.corner_cycles();
+    let edges = cc.edge_cycles();
+    match (corners.as_slice(), edges.as_slice()) {
+        ([c], []) => c.len() == 3,
+        ([], [e]) => e.len() == 3,
+        _ => false,
+    }
+}
+
+/// All 1008 corner and 1760 edge 3-cycles for insertions, with all algorithms for each 3-cycle which are found with
+/// the fewest setup moves.
+///
+/// The commutators `[A, B]` and `[B, A]` of `A = x` or `A = x y x'` and a move `B` give corner 3-cycles, the U
+/// permutations in all orientations and reflections of the cube give edge 3-cycles. The other 3-cycles are
+/// conjugates of these, each setup move is tried on all algorithms of the 3-cycles found with one setup move less. A
+/// 3-cycle keeps all its algorithms since a longer one may cancel more moves when it is inserted.
+fn three_cycles() -> Vec<Vec<Move>> {
+    let mut base: Vec<Vec<Move>> = Vec::new();
+    for x in ALL_MOVES {
+        let mut interchanges = vec![vec![x]];
+        for y in ALL_MOVES.iter().filter(|y| !y.is_same_layer(x)) {
+            interchanges.push(vec![x, *y, x.get_inverse()]);
+        }
+        for a in interchanges {
+            for b in ALL_MOVES {
+                let commutator = cancel_moves(
+                    &[a.clone(), vec![b], invert_moves(&a), vec![b.get_inverse()]].concat(),
+                );
+                base.push(invert_moves(&commutator));
+                base.push(commutator);
+            }
+        }
+    }
+    for (_, alg) in PLL_ALGORITHMS
+        .iter()
+        .filter(|(name, _)| name.starts_with('U'))
+    {
+        let alg = scramble_from_str(alg).unwrap();
+        for s in symmetries::sc().iter() {
+            base.push(alg.iter().map(|m| conjugate_move(s, *m)).collect());
+        }
+    }
+
+    let mut found: HashMap<[u8; 20], Vec<Vec<Move>>> = HashMap::new();
+    for alg in base {
+        let cc = CubieCube::from(&alg);
+        if is_three_cycle(&cc) {
+            let algs = found.entry(cc.to_bytes()).or_default();
+            if !algs.contains(&alg) {
+                algs.push(alg);
+            }
+        }
+    }
+    // the conjugates of a 3-cycle are 3-cycles
+    let mut last: Vec<[u8; 20]> = found.keys().copied().collect();
+    while !last.is_empty() {
+        let mut next: HashMap<[u8; 20], Vec<Vec<Move>>> = HashMap::new();
+        for alg in last.iter().flat_map(|key| &found[key]) {
+            for setup in ALL_MOVES {
+                let conjugate =
+                    cancel_moves(&[vec![setup], alg.clone(), vec![setup.get_inverse()]].concat());
+                let key = CubieCube::from(&conjugate).to_bytes();
+                if !found.contains_key(&key) {
+                    let algs = next.entry(key).or_default();
+                    if !algs.contains(&conjugate) {
+                        algs.push(conjugate);
+                    }
+                }
+            }
+        }
+        last = next.keys().copied().collect();
+        found.extend(next);
+    }
+    let mut algorithms: Vec<Vec<Move>> = found.into_values().flatten().collect();
+    algorithms.sort_by_key(|alg| {
+        (
+            alg.len(),
+            alg.iter().map(|m| *m as usize).collect::<Vec<_>>(),
+        )
+    });
+    algorithms
+}
+
+/// The corner and edge 3-cycles which [find_insertions] uses by default, see [three_cycles].
+pub fn three_cycle_algorithms() -> &'static [Vec<Move>] {
+    &THREE_CYCLES
+}
+
+/// Find the insertions of one of the algorithms into the skeleton which solve the scramble, sorted by the length of the
+/// solution.
+///
+/// The skeleton has to leave a cube which the algorithm solves when inserted somewhere, e.g. a 3-cycle for the
+/// [three_cycle_algorithms].
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::fmc::{find_insertions, three_cycle_algorithms};
+/// use kociemba::scramble::scramble_from_str;
+///
+/// fn main() {
+///     let scramble = CubieCube::from(&scramble_from_str("R U D R U R' D' R U' R' F").unwrap());
+///     // the skeleton leaves a corner 3-cycle
+///     let skeleton = scramble_from_str("F' U' R'").unwrap();
+///     let insertions = find_insertions(&scramble, &skeleton, three_cycle_algorithms());
+///     assert_eq!(scramble.apply_moves(&insertions[0].solution), CubieCube::default());
+/// }
+/// ```
+pub fn find_insertions(
+    scramble: &CubieCube,
+    skeleton: &[Move],
+    algorithms: &[Vec<Move>],
+) -> Vec<Insertion> {
+    let mut by_cube: HashMap<[u8; 20], Vec<&Vec<Move>>> = HashMap::new();
+    for alg in algorithms {
+        by_cube
+            .entry(CubieCube::from(alg).to_bytes())
+            .or_default()
+            .push(alg);
+    }
+    let mut insertions = Vec::new();
+    for position in 0..=skeleton.len() {
+        let (before, after) = skeleton.split_at(position);
+        // the algorithm A with scramble * before * A * after = identity
+        let mut target = scramble.apply_moves(before).inverse_cubie_cube();
+        target.multiply(CubieCube::from(&invert_moves(after)));
+        for alg in by_cube.get(&target.to_bytes()).into_iter().flatten() {
+            let solution = cancel_moves(&[before, alg.as_slice(), after].concat());
+            insertions.push(Insertion {
+                position,
+                algorithm: alg.to_vec(),
+                cancelled: skeleton.len() + alg.len() - solution.len(),
+                solution,
+            });
+        }
+    }
+    insertions.sort_by_key(|i| (i.solution.len(), i.position));
+    insertions
+}
+
+/// The number of corners and edges which are not solved (in place and oriented).
+fn unsolved_pieces(cc: &CubieCube) -> usize {
+    let corners = (0..8).filter(|i| cc.cp[*i] as usize != *i || cc.co[*i] != 0);
+    let edges = (0..12).filter(|i| cc.ep[*i] as usize != *i || cc.eo[*i] != 0);
+    corners.count() + edges.count()
+}
+
+/// Find the insertions of up to max_insertions of the algorithms into the skeleton which solve the scramble, sorted by
+/// the length of the solution.
+///
+/// Each result gives the insertions in the order they are made, an insertion is made into the solution of the one
+/// before it. All insertions but the last reduce the number of unsolved pieces which the skeleton leaves, e.g. a
+/// skeleton which leaves two 3-cycles is solved by two of the [three_cycle_algorithms]. A solution is given once,
+/// with the insertions which are found first.
+pub fn find_multi_insertions(
+    scramble: &CubieCube,
+    skeleton: &[Move],
+    algorithms: &[Vec<Move>],
+    max_insertions: usize,
+) -> Vec<Vec<Insertion>> {
+    // the cubes of the algorithms in the order of the algorithms
+    let mut index: HashMap<[u8; 20], usize> = HashMap::new();
+    let mut cubes: Vec<(CubieCube, Vec<&Vec<Move>>)> = Vec::new();
+    for alg in algorithms {
+        let cc = CubieCube::from(alg);
+        let i = *index.entry(cc.to_bytes()).or_insert_with(|| {
+            cubes.push((cc, Vec::new()));
+            cubes.len() - 1
+        });
+        cubes[i].1.push(alg);
+    }
+    let mut found = Vec::new();
+    insert_next(
+        scramble,
+        skeleton,
+        (&index, &cubes),
+        max_insertions,
+        &mut Vec::new(),
+        &mut found,
+    );
+    found.sort_by_key(|insertions| {
+        let solution = &insertions.last().unwrap().solution;
+        (
+            solution.len(),
+            solution.iter().map(|m| *m as usize).collect::<Vec<_>>(),
+        )
+    });
+    found.dedup_by(|a, b| a.last().unwrap().solution == b.last().unwrap().solution);
+    found
+}
+
+type AlgorithmCubes<'a> = (
+    &'a HashMap<[u8; 20], usize>,
+    &'a [(CubieCube, Vec<&'a Vec<Move>>)],
+);
+
+/// Insert one more algorithm into the skeleton, see [find_multi_insertions]. The last insertion has to solve the cube.
+fn insert_next(
+    scramble: &CubieCube,
+    skeleton: &[Move],
+    (index, cubes): AlgorithmCubes,
+    insertions_left: usize,
+    made: &mut Vec<Insertion>,
+    found: &mut Vec<Vec<Insertion>>,
+) {
+    let remaining = scramble.apply_moves(skeleton);
+    if remaining == CubieCube::default() {
+        if !made.is_empty() {
+            found.push(made.clone());
+        }
+        return;
+    }
+    if insertions_left == 0 {
+        return;
+    }
+    let unsolved = unsolved_pieces(&remaining);
+    for position in 0..=skeleton.len() {
+        let (before, after) = skeleton.split_at(position);
+        let start = scramble.apply_moves(before);
+        let candidates: Vec<usize> = if insertions_left == 1 {
+            // the algorithm A with scramble * before * A * after = identity
+            let mut target = start.inverse_cubie_cube();
+            target.multiply(CubieCube::from(&invert_moves(after)));
+            index.get(&target.to_bytes()).copied().into_iter().collect()
+        } else {
+            // inserting A gives (scramble * before) * A * (scramble * before)^-1 * remaining
+            let start_inverse = start.inverse_cubie_cube();
+            (0..cubes.len())
+                .filter(|i| {
+                    let mut left = start;
+                    left.multiply(cubes[*i].0);
+                    left.multiply(start_inverse);
+                    left.multiply(remaining);
+                    unsolved_pieces(&left) < unsolved
+                })
+                .collect()
+        };
+        for alg in candidates.into_iter().flat_map(|i| &cubes[i].1) {
+            let solution = cancel_moves(&[before, alg.as_slice(), after].concat());
+            made.push(Insertion {
+                position,
+                algorithm: alg.to_vec(),
+                cancelled: skeleton.len() + alg.len() - solution.len(),
+                solution: solution.clone(),
+            });
+            insert_next(
+                scramble,
+                &solution,
+                (index, cubes),
+                insertions_left - 1,
+                made,
+                found,
+            );
+            made.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fmc::*;
+    use crate::moves::Move::*;
+
+    #[test]
+    fn test_cancel_moves() {
+        assert_eq!(cancel_moves(&[R, L, R3]), vec![L]);
+        assert_eq!(cancel_moves(&[U, D, U3, D3, F]), vec![F]);
+        assert_eq!(cancel_moves(&[R, U, U3, R]), vec![R2]);
+        assert_eq!(cancel_moves(&[L, R2, L]), vec![R2, L2]);
+        assert_eq!(cancellations(&[R, U, F], &[F3, U2, R]), 3);
+    }
+
+    #[test]
+    fn test_niss() {
+        let skeleton = NissSkeleton::try_from("R U (F' D) B").unwrap();
+        assert_eq!(skeleton.normal, vec![R, U, B]);
+        assert_eq!(skeleton.inverse, vec![F3, D]);
+        assert_eq!(skeleton.to_string(), "R U B (F' D)");
+        assert_eq!(skeleton.linear(), vec![R, U, B, D3, F]);
+        assert_eq!(skeleton.switched().to_string(), "F' D (R U B)");
+        assert!(NissSkeleton::try_from("R (U").is_err());
+        assert!(NissSkeleton::try_from("R U)").is_err());
+
+        let scramble = CubieCube::from(&vec![R, U, F3, D2, L, B]);
+        // the skeleton of the inverse solution solves all on the inverse
+        let skeleton = NissSkeleton::try_from("(R U F' D2 L)").unwrap();
+        assert_eq!(skeleton.remaining(&scramble), CubieCube::from(&vec![B]));
+        let solution = skeleton
+            .complete(&scramble, &SearchLimits::default())
+            .unwrap();
+        assert_eq!(scramble.apply_moves(&solution), CubieCube::default());
+        assert!(solution.ends_with(&[L3, D2, F, U3, R3]));
+    }
+
+    #[test]
+    fn test_insertions() {
+        let algorithms = three_cycle_algorithms();
+        assert!(algorithms
+            .iter()
+            .all(|alg| is_three_cycle(&CubieCube::from(alg))));
+        // all corner and edge 3-cycles
+        let mut cubes: Vec<CubieCube> = algorithms.iter().map(CubieCube::from).collect();
+        cubes.sort_by_key(|cc| cc.to_bytes());
+        cubes.dedup();
+        let corners = cubes
+            .iter()
+            .filter(|cc| cc.edge_cycles().is_empty())
+            .count();
+        assert_eq!((corners, cubes.len() - corners), (1008, 1760));
+
+        // the skeleton leaves a corner 3-cycle which the commutator solves after its first move
+        let comm = vec![R, U, R3, D, R, U3, R3, D3];
+        let scramble = CubieCube::from(&[vec![R, U], invert_moves(&comm), vec![F]].concat());
+        let skeleton = vec![F3, U3, R3];
+        let insertions = find_insertions(&scramble, &skeleton, algorithms);
+        for insertion in &insertions {
+            assert_eq!(
+                scramble.apply_moves(&insertion.solution),
+                CubieCube::default()
+            );
+        }
+        assert!(insertions[0].solution.len() <= 11);
+        assert!(insertions
+            .iter()
+            .any(|i| i.position == 1 && i.algorithm == comm));
+        // the other algorithms of the 3-cycle are ranked too
+        assert!(insertions
+            .iter()
+            .any(|i| i.position == 1 && i.algorithm != comm));
+
+        // the skeleton leaves an edge 3-cycle with two flipped edges
+        let u_perm = scramble_from_str(PLL_ALGORITHMS[16].1).unwrap();
+        let cycle = cancel_moves(&[vec![F, R3], u_perm, vec![R, F3]].concat());
+        assert!(is_three_cycle(&CubieCube::from(&cycle)));
+        let scramble = CubieCube::from(&[vec![L2, D], cycle.clone(), vec![B]].concat());
+        let skeleton = vec![B3, D3, L2];
+        let insertions = find_insertions(&scramble, &skeleton, algorithms);
+        assert!(!insertions.is_empty());
+        for insertion in &insertions {
+            assert_eq!(
+                scramble.apply_moves(&insertion.solution),
+                CubieCube::default()
+            );
+        }
+
+        // the skeleton leaves a corner and an edge 3-cycle
+        let scramble = CubieCube::from(
+            &[
+                vec![R, U],
+                invert_moves(&comm),
+                vec![F, B],
+                invert_moves(&cycle),
+                vec![D],
+            ]
+            .concat(),
+        );
+        let skeleton = vec![D3, B3, F3, U3, R3];
+        assert!(find_insertions(&scramble, &skeleton, algorithms).is_empty());
+        assert!(find_multi_insertions(&scramble, &skeleton, algorithms, 1).is_empty());
+        let results = find_multi_insertions(&scramble, &skeleton, algorithms, 2);
+        assert!(!results.is_empty());
+        for insertions in &results {
+            assert_eq!(insertions.len(), 2);
+            let solution = &insertions[1].solution;
+            assert_eq!(scramble.apply_moves(solution), CubieCube::default());
+            let inserted = insertions[0].algorithm.len() + insertions[1].algorithm.len();
+            assert_eq!(
+                solution.len(),
+                skeleton.len() + inserted - insertions[0].cancelled - insertions[1].cancelled
+            );
+        }
+        assert!(results[0][1].solution.len() <= skeleton.len() + comm.len() + cycle.len());
+        assert!(results
+            .windows(2)
+            .all(|r| r[0][1].solution.len() <= r[1][1].solution.len()));
+    }
+}
@@ -0,0 +1,1106 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::cubie::{Corner, CubieCube, Edge};
+use crate::error::Error;
+use crate::moves::{Move, Move::*};
+use crate::scramble::scramble_from_str;
+use crate::symmetries::{self, conjugate};
+
+/// OLL algorithms by case number, in face turns without rotations.
+#[rustfmt::skip]
+pub const OLL_ALGORITHMS: [(&str, &str); 57] = [
+    ("1", "R U2 R2 F R F' U2 R' F R F'"),
+    ("2", "F R U R' U' F' B U L U' L' B'"),
+    ("3", "B U L U' L' B' U' F R U R' U' F'"),
+    ("4", "B U L U' L' B' U F R U R' U' F'"),
+    ("5", "L' B2 R B R' B L"),
+    ("6", "L F2 R' F' R F' L'"),
+    ("7", "L F R' F R F2 L'"),
+    ("8", "R' F' L F' L' F2 R"),
+    ("9", "R U R' U' R' F R2 U R' U' F'"),
+    ("10", "R U R' U R' F R F' R U2 R'"),
+    ("11", "L F R' F R' D R D' R F2 L'"),
+    ("12", "R' L R' F' R F' R' F2 R F' R L'"),
+    ("13", "F U R U' R2 F' R U R U' R'"),
+    ("14", "R' F R U R' F' R F U' F'"),
+    ("15", "L' B' L R' U' R U L' B L"),
+    ("16", "L F L' R U R' U' L F' L'"),
+    ("17", "R U R' U R' F R F' U2 R' F R F'"),
+    ("18", "L F R' F R F2 L2 B' R B' R' B2 L"),
+    ("19", "L' R B R B R' B' R' L R' F R F'"),
+    ("20", "L F R' F' R2 L2 B R B' R' B' R' L"),
+    ("21", "R U2 R' U' R U R' U' R U' R'"),
+    ("22", "R U2 R2 U' R2 U' R2 U2 R"),
+    ("23", "R2 D' R U2 R' D R U2 R"),
+    ("24", "L F R' F' L' F R F'"),
+    ("25", "F' L F R' F' L' F R"),
+    ("26", "R U2 R' U' R U' R'"),
+    ("27", "R U R' U R U2 R'"),
+    ("28", "L F R' F' L' R U R U' R'"),
+    ("29", "R U R' U' R U' R' F' U' F R U R'"),
+    ("30", "F R' F R2 U' R' U' R U R' F2"),
+    ("31", "R' U' F U R U' R' F' R"),
+    ("32", "L U F' U' L' U L F L'"),
+    ("33", "R U R' U' R' F R F'"),
+    ("34", "R U R2 U' R' F R U R U' F'"),
+    ("35", "R U2 R2 F R F' R U2 R'"),
+    ("36", "L' U' L U' L' U L U L F' L' F"),
+    ("37", "F R' F' R U R U' R'"),
+    ("38", "R U R' U R U' R' U' R' F R F'"),
+    ("39", "L F' L' U' L U F U' L'"),
+    ("40", "R' F R U R' U' F' U R"),
+    ("41", "R U R' U R U2 R' F R U R' U' F'"),
+    ("42", "R' U' R U' R' U2 R F R U R' U' F'"),
+    ("43", "F' U' L' U L F"),
+    ("44", "F U R U' R' F'"),
+    ("45", "F R U R' U' F'"),
+    ("46", "R' U' R' F R F' U R"),
+    ("47", "R' U' R' F R F' R' F R F' U R"),
+    ("48", "F R U R' U' R U R' U' F'"),
+    ("49", "L F' L2 B L2 F L2 B' L"),
+    ("50", "L' B L2 F' L2 B' L2 F L'"),
+    ("51", "F U R U' R' U R U' R' F'"),
+    ("52", "R U R' U R U' B U' B' R'"),
+    ("53", "R' F2 L F L' F' L F L' F R"),
+    ("54", "L F2 R' F' R F R' F' R F' L'"),
+    ("55", "R' F R U R U' R2 F' R2 U' R' U R U R'"),
+    ("56", "L' B' L U' R' U R U' R' U R L' B L"),
+    ("57", "R U R' U' R' L F R F' L'"),
+];
+
+/// PLL algorithms by case name, in face turns without rotations.
+#[rustfmt::skip]
+pub const PLL_ALGORITHMS: [(&str, &str); 21] = [
+    ("Aa", "R' F R' B2 R F' R' B2 R2"),
+    ("Ab", "R2 B2 R F R' B2 R F' R"),
+    ("E", "R B' R' F R B R' F' R B R' F R B' R' F'"),
+    ("F", "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R"),
+    ("Ga", "R2 U R' U R' U' R U' R2 U' D R' U R D'"),
+    ("Gb", "R' U' R U D' R2 U R' U R U' R U' R2 D"),
+    ("Gc", "R2 U' R U' R U R' U R2 U D' R U' R' D"),
+    ("Gd", "R U R' U' D R2 U' R U' R' U R' U R2 D'"),
+    ("H", "R2 L2 D R2 L2 U2 R2 L2 D R2 L2"),
+    ("Ja", "R' U L' U2 R U' R' U2 R L"),
+    ("Jb", "R U R' F' R U R' U' R' F R2 U' R'"),
+    ("Na", "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'"),
+    ("Nb", "R' U R U' R' F' U' F R U R' F R' F' R U' R"),
+    ("Ra", "R U' R' U' R U R D R' U' R D' R' U2 R'"),
+    ("Rb", "R2 F R U R U' R' F' R U2 R' U2 R"),
+    ("T", "R U R' U' R' F R2 U' R' U' R U R' F'"),
+    ("Ua", "R U' R U R U R U' R' U' R2"),
+    ("Ub", "R2 U R U R' U' R' U' R' U R'"),
+    ("V", "R' U R' U' B' R' B2 U' B' U B' R B R"),
+    ("Y", "F R U' R' U' R U R' F' R U R' U' R' F R F'"),
+    ("Z", "R' L F R2 L2 B R2 L2 F R' L D2 R2 L2"),
+];
+
+/// COLL algorithms by case name, see [recognize_coll]. The algorithms are machine-generated: the shortest maneuvers
+/// found by the two-phase solver which solve the corners up to an AUF and keep the edges oriented, the edges may be
+/// permuted. They are not the fingertrick friendly algorithms of the speedsolving lists.
+///
+/// The table fixes the names of the cases: the case of a name is the case which its algorithm solves.
+#[rustfmt::skip]
+pub const COLL_ALGORITHMS: [(&str, &str); 42] = [
+    ("H 1", "B U2 B' U' B U B' U' B U' B'"),
+    ("H 2", "F' U' F2 R2 D B2 D B2 D2 R2 F'"),
+    ("H 3", "B' U' B U' B' U' F U' B U F'"),
+    ("H 4", "R2 F L2 F' R2 U2 R2 B D2 B' R2"),
+    ("Pi 1", "B U2 B2 U' B2 U' B2 U2 B"),
+    ("Pi 2", "F R2 B2 D B2 U B' D' B U' R2 F'"),
+    ("Pi 3", "F' U B U' F U' B' U' B U' B'"),
+    ("Pi 4", "B' R2 U R2 U' R2 U' F' B U2 F"),
+    ("Pi 5", "R' F2 R U2 R U2 R' F2 U' R U' R'"),
+    ("Pi 6", "R U R2 B2 R2 U' R' U' F2 L' D2 L F2"),
+    ("U 1", "R' U2 R2 B' R' B R' U F' U F R"),
+    ("U 2", "R D' R2 U R' F2 L' U L F2 U' R2 D"),
+    ("U 3", "F2 D F' U2 F D' F' U2 F'"),
+    ("U 4", "F2 D' F U2 F' D F U2 F"),
+    ("U 5", "R2 B2 R F2 R' B2 R F2 R"),
+    ("U 6", "B U2 B' L' U B U B' U' L"),
+    ("T 1", "R' F' U' F U' R B' R B R2 U2 R"),
+    ("T 2", "B L2 D2 R F R' D2 L B' L"),
+    ("T 3", "L' B' R B L B' R' B"),
+    ("T 4", "R B L' B' R' B L B'"),
+    ("T 5", "B L2 B R2 B' L2 B R2 B2"),
+    ("T 6", "F' U F2 D B' R2 B D' F2 U' F"),
+    ("L 1", "L' U' L2 D' L2 U L' B2 U B2 U' L2 D"),
+    ("L 2", "R U2 R D R' U2 R D' R2"),
+    ("L 3", "L' B L F' L' B' L F"),
+    ("L 4", "L' U2 L' D' L U2 L' D L2"),
+    ("L 5", "R B' R' F R B R' F'"),
+    ("L 6", "B L2 F' D F' D' F2 L2 B'"),
+    ("AS 1", "B U2 B' U' B U' B'"),
+    ("AS 2", "R U2 R' U2 L' U R U' R' L"),
+    ("AS 3", "F B' U' B U F' U2 B' U2 B"),
+    ("AS 4", "F' U B U' F U B'"),
+    ("AS 5", "L' B U2 B' L B L' U2 L B'"),
+    ("AS 6", "B2 U2 B D F' L2 F D' B2 U B'"),
+    ("S 1", "F U F' U F U2 F'"),
+    ("S 2", "F' U' L U L' F U L U2 L'"),
+    ("S 3", "L' U2 L U B L' U L U' B'"),
+    ("S 4", "R B' U2 B R' B' R U2 R' B"),
+    ("S 5", "F U' B' U F' U' B"),
+    ("S 6", "B U' B2 D F' L2 F D' B' U2 B2"),
+    ("O 1", "L' B L' F2 L B' L' F2 L2"),
+    ("O 2", "R U R' F2 L D' L' U' L2 D L2 U F2"),
+];
+
+/// ZBLL algorithms by case name, see [recognize_zbll]. The algorithms are machine-generated like [COLL_ALGORITHMS]:
+/// the shortest maneuvers found by the two-phase solver which solve the last layer up to an AUF.
+///
+/// The table fixes the names of the cases: the case of a name is the case which its algorithm solves.
+#[rustfmt::skip]
+pub const ZBLL_ALGORITHMS: [(&str, &str); 493] = [
+    ("H 1", "F' U' L D R' F2 R F' D' L' F2 U2 F U2 F'"),
+    ("H 2", "F' U2 F U F' U' F U F' U F"),
+    ("H 3", "B U2 B' U' B U B' U' B U' B'"),
+    ("H 4", "F2 U D R' U2 D2 L' U2 D2 R' U D B2 D2"),
+    ("H 5", "L U L' U L U' L' U L U2 L'"),
+    ("H 6", "R' U' R U' R' U R U' R' U2 R"),
+    ("H 7", "R U R L2 D R D' R L2 U L F2 L'"),
+    ("H 8", "F' U D' R2 U D' F R2 F' R2 F U2 D2 F"),
+    ("H 9", "B' U2 B2 L' U B2 U B2 U' L U' B'"),
+    ("H 10", "R U2 R' U' B' R U' R' B U' B' U2 B"),
+    ("H 11", "L2 R' F2 L2 F' L2 F' L2 F' R U2 F U2 F'"),
+    ("H 12", "L' U2 L U B L' U L B' U B U2 B'"),
+    ("H 13", "B L' B' L U2 B2 R' F R' F' R2 B2"),
+    ("H 14", "F U F2 L2 D' B2 D' B2 D2 L2 F"),
+    ("H 15", "R' B' R2 U R2 U R2 U2 R2 U B R"),
+    ("H 16", "F2 U R2 B2 D' B D' B' D2 B D' B R2 U' F2"),
+    ("H 17", "F' U' F2 R2 D B2 D B2 D2 R2 F'"),
+    ("H 18", "B' R B R' U2 B2 L F' L F L2 B2"),
+    ("H 19", "B U2 B2 R U' B2 U' B2 U R' U B"),
+    ("H 20", "U2 R F R2 U' R2 U' R2 U2 R2 U' F' R'"),
+    ("H 21", "L2 U' L2 U B L2 U L2 U' B' L2 U L2"),
+    ("H 22", "R2 U R2 U' B' R2 U' R2 U B R2 U' R2"),
+    ("H 23", "F2 D' B L2 B' D F' U F2 U F2 U2 F'"),
+    ("H 24", "L' U2 L2 U L D' L U L' D L' U2 L'"),
+    ("H 25", "L2 F2 L' R2 D R D' F2 R2 U L' U R'"),
+    ("H 26", "B U B' U B U F' U B' U' F"),
+    ("H 27", "R' U L U' R2 U R' L' U2 R U R'"),
+    ("H 28", "F U2 L F' L' U2 R' L2 F' R F L2 F'"),
+    ("H 29", "L U' R' U F2 R2 D R D' R F2 R L'"),
+    ("H 30", "R' U L D' L2 U' L' D' L' D2 R F2 L'"),
+    ("H 31", "F2 D' F U2 F' D F' U F2 U F2 U2 F'"),
+    ("H 32", "B' U' B U' B' U' F U' B U F'"),
+    ("H 33", "R2 B' U' B2 R2 B2 U B2 D2 L2 U2 F L2 D2"),
+    ("H 34", "L F2 D' L D F2 R' U L' U' R F2 L2 F2"),
+    ("H 35", "R B2 L D2 R2 U' R' F2 R U R2 D2 L' B2 R'"),
+    ("H 36", "F R2 F2 U2 F R2 F' U2 F2 R2 F'"),
+    ("H 37", "L2 B U B2 L2 B2 U' B2 D2 R2 U2 F' R2 D2"),
+    ("H 38", "R' F2 L' D2 R2 U R B2 R' U' R2 D2 L F2 R"),
+    ("H 39", "R2 B U' B2 R2 B2 U B2 D2 L2 U2 F' L2 D2"),
+    ("H 40", "R2 F L2 F' R2 U2 R2 B D2 B' R2"),
+    ("Pi 1", "R' L D R2 U2 R D' R2 U2 L' U L2 F2 L2"),
+    ("Pi 2", "R L' B2 D' R2 U2 R' D R2 U2 L' B2 L2"),
+    ("Pi 3", "R L' B2 D L2 U2 L D' L2 U2 R B2 R2"),
+    ("Pi 4", "R U R' U2 L' U2 F2 D' L' D F2 U2 L"),
+    ("Pi 5", "B U2 B2 U' B2 U' B2 U2 B"),
+    ("Pi 6", "L2 F2 L' U2 R2 D R' U2 R2 D' F2 L' R"),
+    ("Pi 7", "R' U' F' U F R2 B U B' U' R'"),
+    ("Pi 8", "L U F U' F' L2 B' U' B U L"),
+    ("Pi 9", "F' R D2 R' F U' F' R D2 R' F"),
+    ("Pi 10", "R2 F2 R U2 L2 D' L U2 L2 D F2 L' R"),
+    ("Pi 11", "B' U2 B2 U B2 U B2 U2 B'"),
+    ("Pi 12", "L' U' L U2 R U2 F2 D R D' F2 U2 R'"),
+    ("Pi 13", "F2 L2 F2 U F' L2 F U' F2 L2 F U F"),
+    ("Pi 14", "R2 B2 R2 U2 R' U2 R' B2 R2 U R' U R"),
+    ("Pi 15", "B U B R2 F2 D' F L2 F' D F2 R2 B2"),
+    ("Pi 16", "B U B' U F2 L2 B' D2 B' D2 B2 L2 F2"),
+    ("Pi 17", "F2 U' F2 L D' F2 D F2 L' D R2 D' R2"),
+    ("Pi 18", "R U2 R' U2 R' F2 R F2 U L' U L"),
+    ("Pi 19", "L' B2 U' R D' R' U R2 D R2 B2 L"),
+    ("Pi 20", "B' R2 B U' B2 L2 F D F D2 F2 L2 B2"),
+    ("Pi 21", "F R2 B2 D B2 U B' D' B U' R2 F'"),
+    ("Pi 22", "R U B' R2 D' F R F' R2 D B R2"),
+    ("Pi 23", "B U B L2 B2 U' B L2 B' U B2 L2 B2"),
+    ("Pi 24", "B U B' U R2 F R2 F' U2 F' U2 F"),
+    ("Pi 25", "L2 U L2 F' U' L2 U L2 F U L2 U' L2"),
+    ("Pi 26", "R' U2 R2 U R U2 L D' F2 D L' U R'"),
+    ("Pi 27", "F L2 F' R' F L2 R U2 L F L' U2 F'"),
+    ("Pi 28", "F' U2 F' D F' U F D' F U F2 U2 F'"),
+    ("Pi 29", "B' U F' U B2 L2 D' B D F' B2 L2 F2"),
+    ("Pi 30", "F U' B' U F' U B U B' U B"),
+    ("Pi 31", "B U2 L U' L U2 L' U' L U' L2 U2 B'"),
+    ("Pi 32", "R U2 R2 U' R2 U' R D' L F2 L' D R2"),
+    ("Pi 33", "F' U B U' F U' B' U' B U' B'"),
+    ("Pi 34", "B' U2 B L2 F' L' F' R' F2 L' F R F'"),
+    ("Pi 35", "R2 U' R2 F U R2 U' R2 F' U' R2 U R2"),
+    ("Pi 36", "B F' L2 B D' B D B2 L2 U B' U' F"),
+    ("Pi 37", "R' U' F U' R2 U R2 U F' R2 U2 R'"),
+    ("Pi 38", "L U F' U L2 U' L2 U' F L2 U2 L"),
+    ("Pi 39", "F2 U F2 L' U L' U2 L U L' U L2 F2 U' F2"),
+    ("Pi 40", "B' U2 B U B' R U R' B U R U2 R'"),
+    ("Pi 41", "R2 B2 L' B' L B' R2 U2 F R' F' R"),
+    ("Pi 42", "B L2 U' L2 U L2 U F B' U2 F'"),
+    ("Pi 43", "R F U R2 U2 R2 U R2 U R2 F' R'"),
+    ("Pi 44", "F U2 F' U2 R' F L2 F L2 F L2 F2 R L2"),
+    ("Pi 45", "B L2 U2 L2 U' L2 U' F U F' U' L2 B'"),
+    ("Pi 46", "L2 B2 R B R' B L2 U2 F' L F L'"),
+    ("Pi 47", "B U2 B' U' B L' U' L B' U' L' U2 L"),
+    ("Pi 48", "B' R2 U R2 U' R2 U' F' B U2 F"),
+    ("Pi 49", "F2 U F2 R' D F2 D' F2 R D' L2 D L2"),
+    ("Pi 50", "B' U' B' R2 B2 U B' R2 B U' B2 R2 B2"),
+    ("Pi 51", "F' L2 B2 D' B2 U' B D B' U L2 F"),
+    ("Pi 52", "B L2 B' U B2 R2 F' D' F' D2 F2 R2 B2"),
+    ("Pi 53", "B' U' B U F2 R2 B2 D2 B' D2 B' R2 F2"),
+    ("Pi 54", "L R D2 L' U2 L D2 L' R' U' R U' R'"),
+    ("Pi 55", "R' F2 R U2 R U2 R' F2 U' R U' R'"),
+    ("Pi 56", "F2 R2 F2 U' F R2 F' U F2 R2 F' U' F'"),
+    ("Pi 57", "L F R' F R F L' F U2 F U2 F'"),
+    ("Pi 58", "B' U' B U' L2 F' L2 F U2 F U2 F'"),
+    ("Pi 59", "R B2 U L' D L U' L2 D' L2 B2 R'"),
+    ("Pi 60", "B' U' B' L2 F2 D F' R2 F D' F2 L2 B2"),
+    ("Pi 61", "R B2 U' B U' B U' B' U2 B' U B2 R'"),
+    ("Pi 62", "R U2 R2 F' R U B F2 D' L' D B' F'"),
+    ("Pi 63", "F L2 F D' L2 B L2 B' D F' L2 U2 F' U2 F'"),
+    ("Pi 64", "R U2 L' U R' U' R' L U' R2 U' R2 U2 R"),
+    ("Pi 65", "R U R2 B2 R2 U' R' U' F2 L' D2 L F2"),
+    ("Pi 66", "R' U2 R2 U R2 U R L' U R U' L U2 R'"),
+    ("Pi 67", "L U L' U' R' U' R U' B2 R B2 R2 U2 R"),
+    ("Pi 68", "R' U2 R' U2 B2 R' D L' B2 L B2 D' R B2 R"),
+    ("Pi 69", "L' U2 L2 F L' U' B' F2 D R D' B F"),
+    ("Pi 70", "R U2 B2 D B D F2 D' B' D L F2 D2 B2"),
+    ("Pi 71", "F' B' D R' D' F2 B U L F' L2 U2 L"),
+    ("Pi 72", "F L' U' F2 U' L U L' F2 U L F'"),
+    ("U 1", "R2 B U' B2 D B2 U B D' B2 U R2"),
+    ("U 2", "B2 D' R2 D B' U2 B D' R2 D B' U2 B'"),
+    ("U 3", "R U2 R2 F R F' R U' B U' B' R'"),
+    ("U 4", "F' U' F U' F' U2 F2 U F' U F U2 F'"),
+    ("U 5", "B U L U' F U' F' U F' L F L2 B'"),
+    ("U 6", "R' U2 R2 B' R' B R' U F' U F R"),
+    ("U 7", "F' U' L' U B' U B U' B L' B' L2 F"),
+    ("U 8", "F R2 B2 R D' R' D B2 R2 F' L U2 L'"),
+    ("U 9", "F2 L2 F' R' F L2 F2 L F R F' L'"),
+    ("U 10", "F2 D R2 D' F U2 F' D R2 D' F U2 F"),
+    ("U 11", "U2 R F' L2 B2 L' D L D' B2 L2 F R'"),
+    ("U 12", "B U B' U B U2 B2 U' B U' B' U2 B"),
+    ("U 13", "R D' R2 U R' F2 L' U L F2 U' R2 D"),
+    ("U 14", "B' U F' U' F U' B U B' R2 B' R2 B2"),
+    ("U 15", "L U L' U L2 B2 D R' F2 R D' B2 L2"),
+    ("U 16", "R2 B2 R' B2 R' U R U' L U' L' U R'"),
+    ("U 17", "R2 U R2 F' U' B2 L2 B' L2 U' B' U2 F R2"),
+    ("U 18", "R' L F2 D' L2 D2 R' D R D2 L F2 R"),
+    ("U 19", "B' R2 B F2 R2 F' U' B U2 F2 U B' F"),
+    ("U 20", "F L' B L2 F' L B D2 R' D2 R' D2 R B2 L2"),
+    ("U 21", "R' L U L2 U2 R U' R' U2 R L2 U2 L'"),
+    ("U 22", "L2 U' L2 F U B2 R2 B R2 U B U2 F' L2"),
+    ("U 23", "L' D L2 U' L F2 R U' R' F2 U L2 D'"),
+    ("U 24", "B L2 F D2 B D B' D2 F2 D' L2 B' F"),
+    ("U 25", "F2 D B' R2 B D' F' U2 F'"),
+    ("U 26", "B U B R2 F' L2 F' D' F L2 F R2 B2"),
+    ("U 27", "F U2 B D2 F' D' F2 D' B' U L2 U F'"),
+    ("U 28", "L2 R D L2 U' L D' L2 U2 B2 U' L R'"),
+    ("U 29", "F2 D2 L2 F2 R2 U F' D2 F U B' L2 B' R2"),
+    ("U 30", "F U2 B' F' U2 L' U R' U2 L U' R B"),
+    ("U 31", "B' F' R2 F' D B2 U' B2 F R2 F' D' B F2"),
+    ("U 32", "R' U L' D B2 D' L U2 R' U' R2 U2 R"),
+    ("U 33", "R' U' R U2 F2 R D R' U R2 D' R2 U' F2"),
+    ("U 34", "F2 D F' U2 F D' F' U2 F'"),
+    ("U 35", "F U' F' U2 F' D' B L2 B' D F"),
+    ("U 36", "R' D2 L2 D R' D2 L' B2 L' D' R2"),
+    ("U 37", "F2 D' B L2 B' D F U2 F"),
+    ("U 38", "F' U F U2 F D B' R2 B D' F'"),
+    ("U 39", "R B' R F' D2 F R' B2 L B L2 B2 R'"),
+    ("U 40", "R U B' R' F' R B R' F U R U2 R'"),
+    ("U 41", "F2 D' F U2 F' D F U2 F"),
+    ("U 42", "B F L2 F D' B2 U B2 F' L2 F D B' F2"),
+    ("U 43", "B2 D2 F2 D F R2 F' D B' D2 F2 D2 B'"),
+    ("U 44", "F' U2 B' D2 F D F2 D B U' R2 U' F"),
+    ("U 45", "L U L' U2 F2 L' D' L U' L2 D L2 U F2"),
+    ("U 46", "F' L' B L F L' B L' F2 L B2 L' F2 L2"),
+    ("U 47", "B' U' B' L2 F R2 F D F' R2 F' L2 B2"),
+    ("U 48", "L D2 R2 D' L D2 R B2 R D L2"),
+    ("U 49", "R2 B2 R F2 R' B2 R F2 R"),
+    ("U 50", "B' F' U2 F' R2 D' B L2 B' D R2 B F2"),
+    ("U 51", "F U2 F D B' R2 B' D' F' D B2 D' F'"),
+    ("U 52", "R2 U' B' R F2 D B2 L B D' F2 B2 R"),
+    ("U 53", "F' D2 U B U' B' D2 L2 D' B D L2 F"),
+    ("U 54", "F D2 U' B' U B D2 R2 D B' D' R2 F'"),
+    ("U 55", "F2 B' D F' R2 F2 D2 B' D' B D2 F B"),
+    ("U 56", "F2 B' D F D2 B2 L2 B D' B D2 F B"),
+    ("U 57", "L2 B2 L' F2 L B2 L' F2 L'"),
+    ("U 58", "B U B' U2 F2 L2 B' D' B' D B2 L2 F2"),
+    ("U 59", "B' U' B U2 F2 R2 B D B D' B2 R2 F2"),
+    ("U 60", "R U2 R D' F2 R' F2 R U F2 D U R'"),
+    ("U 61", "R' F2 D' L2 D' L2 D2 F2 R2 U2 R'"),
+    ("U 62", "B U2 B' L' U B U B' U' L"),
+    ("U 63", "R' U' R U' R' L U' R U R' L' U2 R"),
+    ("U 64", "R L' U B2 U2 L U L D L D' R' L2"),
+    ("U 65", "R L' D L D' R' U2 R D L' D' R' L"),
+    ("U 66", "R B2 D L2 D L2 D2 B2 R2 U2 R"),
+    ("U 67", "R2 L D R' D' R' U' R' U2 F2 U' R L'"),
+    ("U 68", "R U R' U R L' U R' U' R L U2 R'"),
+    ("U 69", "F' U L U' L' U' F L U2 L'"),
+    ("U 70", "R U' B2 U' R2 F2 D L2 D' F2 R2 U2 R'"),
+    ("U 71", "B U' L' U L U B' L' U2 L"),
+    ("U 72", "F' U2 F L U' F' U' F U L'"),
+    ("T 1", "R2 D' R2 U R' F2 U F2 U' R2 D R'"),
+    ("T 2", "R B U B' U R' F R' F' R2 U2 R'"),
+    ("T 3", "B U2 B D' R2 D B' U2 B D' R2 D B2"),
+    ("T 4", "F U2 F' U' F U' F2 U2 F U F' U F"),
+    ("T 5", "F' L2 B L B' U B' U' B U' L U F"),
+    ("T 6", "F' U2 F' D R2 D' F U2 F' D R2 D' F2"),
+    ("T 7", "B L2 F' L' F U' F U F' U L' U' B'"),
+    ("T 8", "R F' L2 B2 D L' D' L B2 L2 F R'"),
+    ("T 9", "R B L' B' R' B2 R2 B' L B R2 B2"),
+    ("T 10", "R' F' U' F U' R B' R B R2 U2 R"),
+    ("T 11", "L U2 L' F R2 B2 D' R D R' B2 R2 F'"),
+    ("T 12", "B' U2 B U B' U B2 U2 B' U' B U' B'"),
+    ("T 13", "R' U R U2 R' L' U R U' L"),
+    ("T 14", "F U2 F' U' F2 U' B' U F' U' F' B"),
+    ("T 15", "R B' R D2 L' F L D2 R2 B"),
+    ("T 16", "R L' U' L' U R' U' L2 U' L' U2 L"),
+    ("T 17", "L' B' R' U2 R U' R' U2 R2 B' R' B2 L"),
+    ("T 18", "B U2 B2 U' B2 U' F U2 B R2 F B2 R2 F2"),
+    ("T 19", "F L' B D L' F' L' F2 L D' L2 B' U2 F'"),
+    ("T 20", "R U R D R' U' R B2 U' B2 R2 D' F2 U F2"),
+    ("T 21", "B' U2 B2 U B2 U F' U2 B' L2 F' B2 L2 F2"),
+    ("T 22", "R B L U2 L' U L U2 L2 B L B2 R'"),
+    ("T 23", "L U' R U R' L' U2 R U R'"),
+    ("T 24", "L2 B2 L R2 B2 R U2 L U' R2 U' R2 U2 R"),
+    ("T 25", "L' B2 L R' U' R U F' L' B2 L F"),
+    ("T 26", "R2 U' F' D2 B U2 B' D2 F R2 U R2"),
+    ("T 27", "L' B' R B R' B R L' B R' B' L2"),
+    ("T 28", "B2 U R F2 R L2 D R' L B2 L F2 R' B2"),
+    ("T 29", "R' U' R U2 B2 U2 B2 U' B2 U' B2 R' U2 R"),
+    ("T 30", "R' U2 R U2 R B' R' U' R' U R B"),
+    ("T 31", "R' U L F2 U F2 U' F2 R L'"),
+    ("T 32", "R U2 L' U' L F2 D R D' F2 U2 R'"),
+    ("T 33", "R2 U' B' L2 F D2 F' L2 B R2 U R2"),
+    ("T 34", "F R F' L F R' F' L'"),
+    ("T 35", "L2 D' L2 U L U L' R B2 L' R' D L"),
+    ("T 36", "F U' R' U2 R U F' R' U R"),
+    ("T 37", "R B2 L R' U L' U' F R B2 R' F'"),
+    ("T 38", "R2 U' R2 U' R2 U R' D' R U R' D R'"),
+    ("T 39", "L' U2 R U R' F2 D' L' D F2 U2 L"),
+    ("T 40", "F2 L2 F D2 B2 D F' D2 B' R2 B' D' L2 F2"),
+    ("T 41", "R B L' B' R' B L B'"),
+    ("T 42", "L U' R' F2 U' F2 U F2 R L'"),
+    ("T 43", "L U2 L' U' B' U' B L F U' F' L'"),
+    ("T 44", "R B L' B' L B' R L' B' L B R2"),
+    ("T 45", "B U' L2 D' B' D L2 B' U L2 B2 L2"),
+    ("T 46", "L U L' U2 B2 U2 B2 U B2 U B2 L U2 L'"),
+    ("T 47", "L2 U F D2 B' U2 B D2 F' L2 U' L2"),
+    ("T 48", "F' U L U2 L' U' F L U' L'"),
+    ("T 49", "B L2 B R2 B' L2 B R2 B2"),
+    ("T 50", "L R D2 R D' L' F2 L2 D2 R' D L2 R'"),
+    ("T 51", "F2 L2 B2 D' B D B L2 F2 U2 B U' B'"),
+    ("T 52", "B L2 R2 D' R F R2 D L2 B R' U' B2"),
+    ("T 53", "F' L2 D' B' D L2 D2 B U B' U' D2 F"),
+    ("T 54", "F R2 D B D' R2 D2 B' U' B U D2 F'"),
+    ("T 55", "R F R' U R U2 R' U R U F' R'"),
+    ("T 56", "B L2 B' U B2 L2 B2 U B U2 B L2 B2"),
+    ("T 57", "B' R2 B' L2 B R2 B' L2 B2"),
+    ("T 58", "L R D2 R D' L D2 R2 B2 R D L2 R'"),
+    ("T 59", "R U' L U' R' U L2 U2 R U' R' U2 L"),
+    ("T 60", "R F' B' D2 B' L' D2 L D2 F L' B2 R'"),
+    ("T 61", "L F2 D F2 L' U' L F2 D' F2 L2 U2 L"),
+    ("T 62", "F U' R F U2 F' U2 R' U F U' F2"),
+    ("T 63", "F' U F2 D B' R2 B D' F2 U' F"),
+    ("T 64", "B D F U' F U D' L2 B D2 F' D2 B2"),
+    ("T 65", "B2 R B U B' U' B' R' B' U' B2 U2 B U2 B"),
+    ("T 66", "L' B2 D' B2 L U L' B2 D B2 L2 U2 L'"),
+    ("T 67", "F' D' B' U B' U' D L2 F' D2 B D2 F2"),
+    ("T 68", "B U' B2 D' F R2 F' D B2 U B'"),
+    ("T 69", "B L2 F' U' L2 U L2 U B' U' F2 U' F'"),
+    ("T 70", "R' U L U' R2 U' L' U R' U' L U2 L'"),
+    ("T 71", "B2 U B2 U B D' B U' B' D B U' B2"),
+    ("T 72", "F B2 U' B' U F' B U B' U2 B U2 B2"),
+    ("L 1", "L' U' L2 D' L2 U L' B2 U B2 U' L2 D"),
+    ("L 2", "B L2 F2 D2 R' D' R D' F L' F L' B'"),
+    ("L 3", "B L2 U2 R' U L2 U' L2 R U2 L2 U' B'"),
+    ("L 4", "R U2 R' U' R U' R' L U L' U L U2 L'"),
+    ("L 5", "R' F' L F' L D' B D' B' D2 L2 F2 R"),
+    ("L 6", "F' B2 D' L2 F R2 F' L2 D B U2 F B"),
+    ("L 7", "F' U' L2 U2 R L2 U' L2 U R' U2 L2 F"),
+    ("L 8", "L R U2 L D B2 R' F2 R B2 D' L2 R'"),
+    ("L 9", "F U' B' U F' U F' D2 F U2 F' D2 F B"),
+    ("L 10", "F R B' R B' D L' D L D2 B2 R2 F'"),
+    ("L 11", "L' B2 R2 D2 F D F' D R' B R' B L"),
+    ("L 12", "F' L F' R' D2 R' F L2 B' L' F2 R2 F' L2 B2"),
+    ("L 13", "R U2 R D L' B2 L D' R2"),
+    ("L 14", "L2 B2 R' F2 R' D R F2 R B2 L' U' L'"),
+    ("L 15", "F U' L2 U' B D F2 D F D2 B' U2 F'"),
+    ("L 16", "F' U2 F U R F' L F R' F' L' U F"),
+    ("L 17", "R U2 R D R' U2 R D' R2"),
+    ("L 18", "F2 B' D F R2 F' B2 U B2 D' F R2 F B"),
+    ("L 19", "R' D2 L2 D2 R' D L' B2 L D L2 D2 R2"),
+    ("L 20", "R' U2 R2 U R U2 L' D B2 D' L U' R"),
+    ("L 21", "F2 D B R2 B D2 F D' B2 D2 F"),
+    ("L 22", "F2 L2 F' R2 F L2 F' R F' L F R F' L'"),
+    ("L 23", "F' D' B L2 B' D F U2 F U F'"),
+    ("L 24", "F2 U R2 D R2 U' R D' R' F2 U2 R' U R"),
+    ("L 25", "R' F' L2 F R U' B' U F' B L2 F"),
+    ("L 26", "B2 U' B2 R' D2 L U2 L' D2 R U B2"),
+    ("L 27", "R2 F L F' L' R F' L F' L' F R"),
+    ("L 28", "R2 B2 U' L' U2 R' B2 R' U R2 U2 L B2 R2"),
+    ("L 29", "F R B R' F' R B' R'"),
+    ("L 30", "R' L F2 U F2 U' F2 L' U' R"),
+    ("L 31", "L' B' U' B L F U' F' U' L' U2 L"),
+    ("L 32", "B U2 R2 D B' D' R2 F' U F U2 B'"),
+    ("L 33", "R' U' R F U' R' U2 R U F'"),
+    ("L 34", "R' U2 R B2 U B2 U B2 U2 B2 U2 R' U R"),
+    ("L 35", "B' D B' U B D' B' U B2 U' B2 U' B2"),
+    ("L 36", "L2 F2 L2 U F' L2 D F' D' L2 U' F"),
+    ("L 37", "L' U2 L' D' R B2 R' D L2"),
+    ("L 38", "F D B' R2 B D' F' U2 F' U' F"),
+    ("L 39", "R B2 L2 B' L' B2 R F' D2 F R' B R'"),
+    ("L 40", "F U2 F' U' L' F R' F' L F R U' F'"),
+    ("L 41", "F2 L' F2 L' D R U2 R' D B2 R2 B2 D2 L2"),
+    ("L 42", "R B U' F U2 B' U F' U2 R' L' U2 L"),
+    ("L 43", "F2 B D' F' L2 F B2 U' B2 D F' L2 F' B'"),
+    ("L 44", "F' U R2 U B' D' F2 D' F' D2 B U2 F"),
+    ("L 45", "F2 D' B' L2 B' D2 F' D B2 D2 F'"),
+    ("L 46", "L' U2 L' D' L U2 L' D L2"),
+    ("L 47", "R2 B2 L F2 L D' L' F2 L' B2 R U R"),
+    ("L 48", "F2 U' L2 D' L2 U L' D L F2 U2 L U' L'"),
+    ("L 49", "R B' R' F R B R' F'"),
+    ("L 50", "F D R2 F2 U F B U2 B' U F2 D' F2"),
+    ("L 51", "L U L' F' U L U2 L' U' F"),
+    ("L 52", "B' U2 L2 D' B D L2 F U' F' U2 B"),
+    ("L 53", "R2 B' L2 F R2 B' F D B F2 L2 B U R2"),
+    ("L 54", "B2 U B2 L D2 R' U2 R D2 L' U' B2"),
+    ("L 55", "R' L F2 U' F2 U F2 R U L'"),
+    ("L 56", "L F R2 F' L' U B U' F B' R2 F'"),
+    ("L 57", "L U2 L' B2 U' B2 U' B2 U2 B2 U2 L U' L'"),
+    ("L 58", "B2 U B2 R F2 L' D2 L F2 R' U' B2"),
+    ("L 59", "R B U B' U' B' R' F R2 B R2 F'"),
+    ("L 60", "L F2 D' L D' B2 L' D2 F2 L' U R'"),
+    ("L 61", "R' F' U' F2 R' F' R F' U F R"),
+    ("L 62", "B' R2 F2 D' F' D F' R2 B"),
+    ("L 63", "R' F R2 D2 L B L' D2 R F' R2"),
+    ("L 64", "B' D' F' U2 F' D B' D' F2 R2 D B2"),
+    ("L 65", "L' B' R' U2 R B L2 F' L' F2 U2 F'"),
+    ("L 66", "B U L U R U R' U L' F' B' U2 F"),
+    ("L 67", "R2 F2 R' B2 L2 D L D' L B2 R F2 R2"),
+    ("L 68", "F R' F2 D2 B' L' B D2 F' R F2"),
+    ("L 69", "B L2 F B2 U F' U' B2 L2 B'"),
+    ("L 70", "B L F U2 F' L' B2 R B R2 U2 R"),
+    ("L 71", "B L2 F' D F' D' F2 L2 B'"),
+    ("L 72", "B' R2 B2 U' F' U F B2 R2 B"),
+    ("AS 1", "R' U L U' D' F2 D R2 U2 L' U R'"),
+    ("AS 2", "L2 B2 R D' R D' R' D2 R' B2 L2"),
+    ("AS 3", "R' U' R U' R' U2 R"),
+    ("AS 4", "R' F' L F R F2 D2 B R' B' D2 F2 L'"),
+    ("AS 5", "R2 U R2 U R' U' R2 U R2 U' R U' R2"),
+    ("AS 6", "F2 U' F U' F2 U F2 U' F' U F2 U F2"),
+    ("AS 7", "L' U R' U' R2 D B2 D' R' L"),
+    ("AS 8", "F2 R2 B' D2 B' D' B D' B R2 F2"),
+    ("AS 9", "L U L' U L' U' L2 U' L2 U2 L"),
+    ("AS 10", "F' B D' L2 D F2 U' F' U B'"),
+    ("AS 11", "B U2 B' U' B U' B'"),
+    ("AS 12", "R' U' R2 U R U R' U' R' U' R2 U2 R"),
+    ("AS 13", "B' F R2 U R2 U' R2 U' B U F'"),
+    ("AS 14", "B' U2 B' D' B U B' D B2 U' B' U2 B"),
+    ("AS 15", "R' U' R U' R2 D' L F2 L' D R2"),
+    ("AS 16", "R U2 R' U2 L' U R U' R' L"),
+    ("AS 17", "L2 R' D2 R B' D2 L2 U2 F' R F R F'"),
+    ("AS 18", "F' L U L2 U L2 U2 L2 U L F"),
+    ("AS 19", "B' R2 B R2 D' R2 D B2 U' B2 F' U2 F"),
+    ("AS 20", "R2 F2 R U' R2 B2 L' D' R' L' B2 L' U2 L'"),
+    ("AS 21", "R' U' R U' R2 D' R U2 R' D R2"),
+    ("AS 22", "R' U2 B' U F U' B U2 F' R F U' F'"),
+    ("AS 23", "F U F' U B U' L2 U' L2 U L2 B'"),
+    ("AS 24", "B F U2 B2 D' R2 D B2 U F' U B'"),
+    ("AS 25", "R' U L U' F2 U' F2 U F2 R L'"),
+    ("AS 26", "B' R2 U R2 U' R2 U' B U F' U F"),
+    ("AS 27", "B' U2 B' L2 B' F' D' B' L2 F2 U' F R2 F2"),
+    ("AS 28", "F B' U' B U F' U2 B' U2 B"),
+    ("AS 29", "L' B L B R' F2 D2 B2 L' B D2 F2 B'"),
+    ("AS 30", "B L U L2 U2 L2 U L2 U L B'"),
+    ("AS 31", "B U2 F2 B' U' F2 D R2 D' R2 F R2 F'"),
+    ("AS 32", "F2 D B' R2 B D' F2 U' F U' F'"),
+    ("AS 33", "R' U L' U R2 D B2 D' R2 U2 R L"),
+    ("AS 34", "R2 D' R2 U R' D' B F2 D F2 D' B' D2 R"),
+    ("AS 35", "R U2 R' U' R2 D R' U R D' R' U2 R'"),
+    ("AS 36", "F2 D F' U2 F D' F2 U' F U' F'"),
+    ("AS 37", "F' U B U' F U B'"),
+    ("AS 38", "R B2 L' B2 D' B2 D L R' U L U L'"),
+    ("AS 39", "F2 D' L2 B D F D' B2 U2 B D F"),
+    ("AS 40", "F2 D R' U D' R' U' R2 F2 U' F2 R' F2"),
+    ("AS 41", "B' U2 R U R' B R U' R' B' U2 B"),
+    ("AS 42", "R U2 B R' U' R U R B' R' U2 R'"),
+    ("AS 43", "F2 D' B L2 B' D F U F U' F' U2 F"),
+    ("AS 44", "F' U F U F B' D R2 D' R2 F' R2 B"),
+    ("AS 45", "F2 R2 L2 B D F D' B D F R2 L2 B2"),
+    ("AS 46", "L U2 L' U' L U L D R' F2 R D' L2"),
+    ("AS 47", "L D R U2 R2 D' L D R F2 D' L2"),
+    ("AS 48", "B' U L2 D2 F' R2 D' F D' L2"),
+    ("AS 49", "L' B U2 B' L B L' U2 L B'"),
+    ("AS 50", "L' R' U2 L U L' U2 R2 U' L U R'"),
+    ("AS 51", "R U2 R F2 D L' B2 L D' F2 R2"),
+    ("AS 52", "R B' R2 L2 D2 L' F' L D2 R' L2 B R2"),
+    ("AS 53", "F' D F2 U R2 U R2 F' U2 F2 D' F2"),
+    ("AS 54", "L2 D' L2 U2 L' B2 U B2 U L2 D L'"),
+    ("AS 55", "F U R2 U F2 U' F2 U' R2 F2 U2 F"),
+    ("AS 56", "R' U L U' R2 U2 L' U L U2 R' L'"),
+    ("AS 57", "R' U L U L2 U2 R U L' B2 L2 B2"),
+    ("AS 58", "R U2 R2 F2 U' R2 U' R2 U F2 U R"),
+    ("AS 59", "F2 R2 D' B L2 B' D R2 F U2 F"),
+    ("AS 60", "R2 B2 U B' U' D B' D' R2 U R2 B' R2"),
+    ("AS 61", "F R2 F L2 B' D' B' D B2 L2 F' R2 F'"),
+    ("AS 62", "L' U L2 D' R B2 R' D L U2 L2"),
+    ("AS 63", "B' R2 B U' B2 L2 F D' F' L2 F' B2 U2 F"),
+    ("AS 64", "L F U F' U' R' L' F' L F' L' F2 R"),
+    ("AS 65", "L' B' L F' R' L' B L B' R F B"),
+    ("AS 66", "F U R2 U F2 U2 B U F2 U' B' R2 F2 U2 F"),
+    ("AS 67", "B' R2 F2 R2 U F U' R2 F' R2 F2 B U2 F"),
+    ("AS 68", "B U2 B' F2 L2 B' D' B L2 F2 U' F R2 F'"),
+    ("AS 69", "B U B' U F' U2 F R2 F R2 F2 U2 F"),
+    ("AS 70", "F' U2 R B' R' B U2 F R U2 R2 U2 R"),
+    ("AS 71", "B2 U2 B D F' L2 F D' B2 U B'"),
+    ("AS 72", "B' R2 F2 D2 F' D2 B R2 F' U F' U F"),
+    ("S 1", "F U' B U2 F2 D' L2 U D B' U' F"),
+    ("S 2", "L' U2 L U L' U L"),
+    ("S 3", "F U F2 U' F2 U' F2 U2 F2 U2 F'"),
+    ("S 4", "R2 L2 B D B' R2 L2 U F' U2 F2 U2 F"),
+    ("S 5", "R L' D B2 D' R2 U R U' L"),
+    ("S 6", "B U' F U F2 D' L2 D F B'"),
+    ("S 7", "R2 U R' U R2 U' R2 U R U' R2 U' R2"),
+    ("S 8", "F U F' U F U2 F'"),
+    ("S 9", "B' U' B U' B U B2 U B2 U2 B'"),
+    ("S 10", "F U F' L2 B' U' B U L2 F U' F'"),
+    ("S 11", "F' U2 F2 U2 F2 U' F2 U' F2 U F"),
+    ("S 12", "F U F2 U' F' U' F U F U F2 U2 F'"),
+    ("S 13", "L U' R' U F2 U F2 U' F2 R L'"),
+    ("S 14", "L' U2 L U L2 D' L U' L' D L U2 L"),
+    ("S 15", "F2 D' B L2 B' D F2 U F' U F"),
+    ("S 16", "F' U' L U L' F U L U2 L'"),
+    ("S 17", "F U F' R' F U2 B' U F' U' B U2 R"),
+    ("S 18", "R' L D F2 D' L2 U' R U' R' U2 R L"),
+    ("S 19", "B' R' U' R2 U2 R2 U' R2 U' R' B"),
+    ("S 20", "B U2 B R2 B F D B R2 F2 U F' L2 F2"),
+    ("S 21", "L U' R U' L2 D' B2 D L2 U2 R' L'"),
+    ("S 22", "R B' R' B' R U2 F2 D2 L B' D2 F2 B"),
+    ("S 23", "B L2 U' L2 U L2 U B' U' F U' F'"),
+    ("S 24", "F2 D' F U2 F' D F2 U F' U F"),
+    ("S 25", "B F' L2 U' L2 U L2 U B' U' F"),
+    ("S 26", "F' U' F U' B' U R2 U R2 U' R2 B"),
+    ("S 27", "L2 F2 L' U L2 B2 R D R L B2 R U2 R"),
+    ("S 28", "L' U2 L U B L' U L U' B'"),
+    ("S 29", "L U2 B U' F' U B' U2 F L' F' U F"),
+    ("S 30", "B F U2 B' U' B U' F2 D' L2 D B' F"),
+    ("S 31", "F R' U' R2 U' R2 U2 R2 U' R' F'"),
+    ("S 32", "L U L' U L2 D R' F2 R D' L2"),
+    ("S 33", "L U L' U L2 D L' U2 L D' L2"),
+    ("S 34", "L R2 D2 L' B D2 R2 U2 F L' F' L' F"),
+    ("S 35", "B U2 B D B' U' B D' B2 U B U2 B'"),
+    ("S 36", "B' F' U2 B2 D L2 D' B2 U' F U' B"),
+    ("S 37", "R B' U2 B R' B' R U2 R' B"),
+    ("S 38", "F2 L2 D B' R2 B D' L2 F' U2 F'"),
+    ("S 39", "L U' R' U L2 U2 R U' R' U2 R L"),
+    ("S 40", "U2 R' F R2 L2 D2 L B L' D2 R L2 F' R2"),
+    ("S 41", "L' U2 L2 F2 U L2 U L2 U' F2 U' L'"),
+    ("S 42", "F' U' L2 U' F2 U F2 U L2 F2 U2 F'"),
+    ("S 43", "R2 D R2 U2 R B2 U' B2 U' R2 D' R"),
+    ("S 44", "L' U2 L' F2 D' R B2 R' D F2 L2"),
+    ("S 45", "F2 L' F' U2 F L F' U2 F' R U2 R'"),
+    ("S 46", "F D' F2 U' L2 U' L2 F U2 F2 D F2"),
+    ("S 47", "L R U2 R' U' R U2 L2 U R' U' L"),
+    ("S 48", "L2 B2 U' B U D' B D L2 U' L2 B L2"),
+    ("S 49", "F U' B' U F' U' B"),
+    ("S 50", "R' D' L' U2 L2 D R' D' L' F2 D R2"),
+    ("S 51", "F U' B' U D R2 D' F' U' F' U2 F B"),
+    ("S 52", "F2 D' L U' D L U L2 F2 U F2 L F2"),
+    ("S 53", "R' U2 R U R' U' R' D' L F2 L' D R2"),
+    ("S 54", "F2 D B' R2 B D' F' U' F' U F U2 F'"),
+    ("S 55", "L' U2 L B U B' L' B U' B' U2 L"),
+    ("S 56", "F2 D R2 B' D' F' D B2 U2 B' D' F'"),
+    ("S 57", "F U' B' U2 F2 U' F' U' B U L2 F2 L2"),
+    ("S 58", "B U2 L' U' L B' L' U L B U2 B'"),
+    ("S 59", "B F U2 B' U' B' D' R2 D U F' U' B"),
+    ("S 60", "B U' R2 D2 F L2 D F' D R2"),
+    ("S 61", "R' F2 R' B2 L D L D' L2 B2 R F2 R"),
+    ("S 62", "L2 U2 L' D' R B2 R' D L2 U' L"),
+    ("S 63", "L' U2 L R2 B2 L D L' B2 R2 U R' F2 R"),
+    ("S 64", "B' R' U' R U F B R B' R B R2 F'"),
+    ("S 65", "R U R' U R' F2 D2 L2 D L2 D F2 R"),
+    ("S 66", "F' U2 F U2 B L2 D F' D' L2 F' B' U2 F"),
+    ("S 67", "R' F' U' F U F2 L2 F' R F2 L2 B' U2 F B"),
+    ("S 68", "R B' L' B' R' L F R2 B R2 F' L U2 L'"),
+    ("S 69", "L' U' L U' R U2 R' F2 R' F2 R2 U2 R'"),
+    ("S 70", "B L B' R F B L' B' L F' R' L'"),
+    ("S 71", "B U' B2 D F' L2 F D' B' U2 B2"),
+    ("S 72", "L F2 R2 D2 R D2 L' F2 R U' R U' R'"),
+    ("O 1", "L2 U F' B L2 F B' U L2"),
+    ("O 2", "L2 U' F' B L2 F B' U' L2"),
+    ("O 3", "R L' U2 D2 R L' D' R2 L2 U F2 B2"),
+    ("O 4", "R2 F2 B2 L2 D R2 F2 B2 L2"),
+    ("O 5", "B2 D B D' B R2 F' U F R2"),
+    ("O 6", "B2 L' B' U' B' U B L B' U2 B U2 B'"),
+    ("O 7", "B2 U B2 D' R2 F2 U' F2 D R2"),
+    ("O 8", "R' U2 R L' U L U2 R' U' F2 R' F2 R2"),
+    ("O 9", "R B' R F2 R' B R F2 R2"),
+    ("O 10", "R2 B2 U' R2 D R2 D' F2 U F2 B2 R2"),
+    ("O 11", "L' R' U2 L R F U' B U2 F' U B'"),
+    ("O 12", "R' L F2 L D' R F2 L' U R2 L' B2 R2"),
+    ("O 13", "R' U L' U2 R U' L F B U2 F' B'"),
+    ("O 14", "L' B L' F2 L B' L' F2 L2"),
+    ("O 15", "B2 D' B' D B' L2 F U' F' L2"),
+    ("O 16", "B2 L2 R2 U L2 D' B2 D B2 U' R2 B2"),
+    ("O 17", "F U' B' U F' U' F U' B U F' U2 B' U2 B"),
+    ("O 18", "R U R' F2 L D' L' U' L2 D L2 U F2"),
+    ("O 19", "R2 B U D' R2 U D B2 R2 F' U2 F2 R2 F' U2 B2"),
+    ("O 20", "R F R B' R' F' B U' B L' B L B2 U2 R'"),
+    ("O 21", "R U R' B2 R U' R L2 D' F2 D R2 L2 B2 U B2"),
+];
+
+lazy_static! {
+    static ref OLL: Vec<(&'static str, Vec<Move>)> = parse_algorithms(&OLL_ALGORITHMS);
+    static ref PLL: Vec<(&'static str, Vec<Move>)> = parse_algorithms(&PLL_ALGORITHMS);
+    static ref COLL: Vec<(&'static str, Vec<Move>)> = parse_algorithms(&COLL_ALGORITHMS);
+    static ref ZBLL: Vec<(&'static str, Vec<Move>)> = parse_algorithms(&ZBLL_ALGORITHMS);
+    /// The rotations around the U axis, the symmetries 0, 2, 4 and 6.
+    static ref U_ROTATIONS: Vec<CubieCube> = symmetries::sc().into_iter().step_by(2).take(4).collect();
+    /// The index into [COLL_ALGORITHMS] of the COLL cases by their key.
+    static ref COLL_CASES: HashMap<Vec<u8>, usize> = case_indices(&COLL, false);
+    /// The index into [ZBLL_ALGORITHMS] of the ZBLL cases by their key.
+    static ref ZBLL_CASES: HashMap<Vec<u8>, usize> = case_indices(&ZBLL, true);
+}
+
+/// The algorithm sets of the last layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlgorithmSet {
+    Oll,
+    Pll,
+    Coll,
+    Zbll,
+}
+
+impl fmt::Display for AlgorithmSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AlgorithmSet::Oll => "OLL",
+            AlgorithmSet::Pll => "PLL",
+            AlgorithmSet::Coll => "COLL",
+            AlgorithmSet::Zbll => "ZBLL",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A recognized last layer case.
+///
+/// * `name`: The case name, e.g. `OLL 27`, `PLL T`, `COLL Pi 3` or `ZBLL U 12`, or e.g. `PLL skip` for a cube
+///   without a case of the set.
+/// * `pre_auf`, `algorithm`, `post_auf`: The moves which solve the case: the U turn before the algorithm, the algorithm
+///   and the U turn after it. None for no turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastLayerCase {
+    pub set: AlgorithmSet,
+    pub name: String,
+    pub pre_auf: Option<Move>,
+    pub algorithm: Vec<Move>,
+    pub post_auf: Option<Move>,
+}
+
+impl LastLayerCase {
+    /// The moves of the solution: the pre-AUF, the algorithm and the post-AUF.
+    pub fn solution(&self) -> Vec<Move> {
+        self.pre_auf
+            .iter()
+            .chain(&self.algorithm)
+            .chain(&self.post_auf)
+            .copied()
+            .collect()
+    }
+}
+
+fn parse_algorithms(algorithms: &[(&'static str, &str)]) -> Vec<(&'static str, Vec<Move>)> {
+    algorithms
+        .iter()
+        .map(|(name, alg)| (*name, scramble_from_str(alg).unwrap()))
+        .collect()
+}
+
+/// The U turn of k quarter turns, None for no turn.
+fn auf(k: usize) -> Option<Move> {
+    [None, Some(U), Some(U2), Some(U3)][k % 4]
+}
+
+fn apply_auf(cc: &CubieCube, k: usize) -> CubieCube {
+    match auf(k) {
+        Some(m) => cc.apply_move(m),
+        None => *cc,
+    }
+}
+
+/// Check if the corners and edges of the D and the middle layer are solved.
+pub fn is_f2l_solved(cc: &CubieCube) -> bool {
+    (Corner::DFR as usize..8).all(|i| cc.cp[i] as usize == i && cc.co[i] == 0)
+        && (Edge::DR as usize..12).all(|i| cc.ep[i] as usize == i && cc.eo[i] == 0)
+}
+
+fn is_corners_oriented(cc: &CubieCube) -> bool {
+    cc.co[..4].iter().all(|o| *o == 0)
+}
+
+fn is_edges_oriented(cc: &CubieCube) -> bool {
+    cc.eo[..4].iter().all(|o| *o == 0)
+}
+
+/// The post-AUF which solves the cube, None if no U turn solves it.
+fn solving_auf(cc: &CubieCube) -> Option<usize> {
+    (0..4).find(|k| apply_auf(cc, *k) == CubieCube::default())
+}
+
+/// The post-AUF which solves the first n bytes of the encoding of the cube, the corners for n = 8.
+fn aligning_auf(cc: &CubieCube, n: usize) -> Option<usize> {
+    let solved = CubieCube::default().to_bytes();
+    (0..4).find(|k| apply_auf(cc, *k).to_bytes()[..n] == solved[..n])
+}
+
+fn check_f2l(cc: &CubieCube) -> Result<(), Error> {
+    cc.verify()?;
+    if !is_f2l_solved(cc) {
+        return Err(Error::F2LNotSolved);
+    }
+    Ok(())
+}
+
+/// Recognize the OLL case of a cube with solved F2L.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::lastlayer::recognize_oll;
+/// use kociemba::scramble::scramble_from_str;
+///
+/// fn main() {
+///     // the inverse of Sune after a U turn
+///     let cc = CubieCube::from(&scramble_from_str("U R U2 R' U' R U' R'").unwrap());
+///     let case = recognize_oll(&cc).unwrap();
+///     assert_eq!(case.name, "OLL 27");
+///     println!("{:?} {:?}", case.pre_auf, case.algorithm);
+/// }
+/// ```
+pub fn recognize_oll(cc: &CubieCube) -> Result<LastLayerCase, Error> {
+    check_f2l(cc)?;
+    let case = |name: String, pre_auf, algorithm| LastLayerCase {
+        set: AlgorithmSet::Oll,
+        name,
+        pre_auf,
+        algorithm,
+        post_auf: None,
+    };
+    if is_corners_oriented(cc) && is_edges_oriented(cc) {
+        return Ok(case("OLL skip".to_string(), None, Vec::new()));
+    }
+    for k in 0..4 {
+        let pre = apply_auf(cc, k);
+        for (name, alg) in OLL.iter() {
+            let oriented = pre.apply_moves(alg);
+            if is_corners_oriented(&oriented) && is_edges_oriented(&oriented) {
+                return Ok(case(format!("OLL {}", name), auf(k), alg.clone()));
+            }
+        }
+    }
+    Err(Error::InvalidLastLayerCase)
+}
+
+/// Recognize the PLL case of a cube with solved F2L and oriented last layer.
+pub fn recognize_pll(cc: &CubieCube) -> Result<LastLayerCase, Error> {
+    check_f2l(cc)?;
+    if !is_corners_oriented(cc) || !is_edges_oriented(cc) {
+        return Err(Error::InvalidLastLayerCase);
+    }
+    let case = |name: String, pre_auf, algorithm, post_auf| LastLayerCase {
+        set: AlgorithmSet::Pll,
+        name,
+        pre_auf,
+        algorithm,
+        post_auf,
+    };
+    if let Some(post) = solving_auf(cc) {
+        return Ok(case("PLL skip".to_string(), None, Vec::new(), auf(post)));
+    }
+    for k in 0..4 {
+        let pre = apply_auf(cc, k);
+        for (name, alg) in PLL.iter() {
+            if let Some(post) = solving_auf(&pre.apply_moves(alg)) {
+                return Ok(case(
+                    format!("PLL {}", name),
+                    auf(k),
+                    alg.clone(),
+                    auf(post),
+                ));
+            }
+        }
+    }
+    Err(Error::InvalidLastLayerCase)
+}
+
+/// Recognize the COLL case of a cube with solved F2L and oriented last layer edges.
+///
+/// The COLL cases are named by the OCLL shape of the corner orientation (H, Pi, U, T, L, AS, S or O for oriented
+/// corners) and a number within the shape. The numbers are fixed by the table [COLL_ALGORITHMS] of this crate, they
+/// do not follow a published list. The machine-generated algorithm solves the corners and keeps the edges oriented,
+/// the edges may still need an EPLL.
+pub fn recognize_coll(cc: &CubieCube) -> Result<LastLayerCase, Error> {
+    recognize_by_key(cc, AlgorithmSet::Coll, &COLL_CASES, &COLL)
+}
+
+/// Recognize the ZBLL case of a cube with solved F2L and oriented last layer edges.
+///
+/// The ZBLL cases are named like the COLL cases with the numbers fixed by [ZBLL_ALGORITHMS], the machine-generated
+/// algorithm solves the cube.
+pub fn recognize_zbll(cc: &CubieCube) -> Result<LastLayerCase, Error> {
+    recognize_by_key(cc, AlgorithmSet::Zbll, &ZBLL_CASES, &ZBLL)
+}
+
+fn recognize_by_key(
+    cc: &CubieCube,
+    set: AlgorithmSet,
+    cases: &HashMap<Vec<u8>, usize>,
+    algorithms: &[(&'static str, Vec<Move>)],
+) -> Result<LastLayerCase, Error> {
+    check_f2l(cc)?;
+    if !is_edges_oriented(cc) {
+        return Err(Error::InvalidLastLayerCase);
+    }
+    let with_edges = set == AlgorithmSet::Zbll;
+    let key = case_key(cc, with_edges);
+    let case = |name: String, pre_auf, algorithm, post_auf| LastLayerCase {
+        set,
+        name,
+        pre_auf,
+        algorithm,
+        post_auf,
+    };
+    if key == solved_key(with_edges) {
+        // the corners (and edges for ZBLL) are solved up to an AUF
+        let post = aligning_auf(cc, key.len());
+        return Ok(case(
+            format!("{} skip", set),
+            None,
+            Vec::new(),
+            post.and_then(auf),
+        ));
+    }
+    let (name, alg) = &algorithms[cases[&key]];
+    for k in 0..4 {
+        let solved = apply_auf(cc, k).apply_moves(alg);
+        if !is_f2l_solved(&solved) || !is_edges_oriented(&solved) {
+            continue;
+        }
+        if let Some(post) = aligning_auf(&solved, key.len()) {
+            return Ok(case(
+                format!("{} {}", set, name),
+                auf(k),
+                alg.clone(),
+                auf(post),
+            ));
+        }
+    }
+    Err(Error::InvalidLastLayerCase)
+}
+
+/// The key of the case of a last layer: the smallest encoding of the cube conjugated by the four rotations around
+/// the U axis and followed by the four AUFs. Only the corners count for COLL.
+fn case_key(cc: &CubieCube, with_edges: bool) -> Vec<u8> {
+    let n = if with_edges { 20 } else { 8 };
+    U_ROTATIONS
+        .iter()
+        .flat_map(|s| {
+            let rotated = conjugate(s, cc);
+            (0..4).map(move |k| apply_auf(&rotated, k).to_bytes()[..n].to_vec())
+        })
+        .min()
+        .unwrap()
+}
+
+fn solved_key(with_edges: bool) -> Vec<u8> {
+    case_key(&CubieCube::default(), with_edges)
+}
+
+/// The cases of the algorithms by their key.
+fn case_indices(
+    algorithms: &[(&'static str, Vec<Move>)],
+    with_edges: bool,
+) -> HashMap<Vec<u8>, usize> {
+    algorithms
+        .iter()
+        .enumerate()
+        .map(|(i, (_, alg))| {
+            let case = CubieCube::from(alg).inverse_cubie_cube();
+            (case_key(&case, with_edges), i)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lastlayer::*;
+
+    /// The cube which is solved by the moves.
+    fn case_of(moves: &[Move]) -> CubieCube {
+        CubieCube::from(&moves.to_vec()).inverse_cubie_cube()
+    }
+
+    #[test]
+    fn test_oll() {
+        for (name, alg) in OLL.iter() {
+            let cc = case_of(alg);
+            assert!(is_f2l_solved(&cc));
+            assert_eq!(recognize_oll(&cc).unwrap().name, format!("OLL {}", name));
+        }
+        // all orientations of the last layer
+        for twist in 0..27u8 {
+            for flip in 0..8u8 {
+                let mut cc = CubieCube::default();
+                cc.co[..3].copy_from_slice(&[twist % 3, twist / 3 % 3, twist / 9]);
+                cc.co[3] = (6 - cc.co[..3].iter().sum::<u8>()) % 3;
+                cc.eo[..3].copy_from_slice(&[flip & 1, flip >> 1 & 1, flip >> 2]);
+                cc.eo[3] = cc.eo[..3].iter().sum::<u8>() % 2;
+                let case = recognize_oll(&cc).unwrap();
+                let oriented = cc.apply_moves(&case.solution());
+                assert!(is_corners_oriented(&oriented) && is_edges_oriented(&oriented));
+            }
+        }
+        let cc = CubieCube::from(&vec![U3, R, U2, R3, U3, R, U3, R3]);
+        let case = recognize_oll(&cc).unwrap();
+        assert_eq!(case.name, "OLL 27");
+        assert_eq!(
+            recognize_oll(&CubieCube::from(&vec![U])).unwrap().name,
+            "OLL skip"
+        );
+        assert!(recognize_oll(&CubieCube::from(&vec![R])).is_err());
+    }
+
+    #[test]
+    fn test_pll() {
+        for (name, alg) in PLL.iter() {
+            let case = recognize_pll(&case_of(alg)).unwrap();
+            assert_eq!(case.name, format!("PLL {}", name));
+            assert_eq!((case.pre_auf, case.post_auf), (None, None));
+        }
+        let t_perm = scramble_from_str(PLL_ALGORITHMS[15].1).unwrap();
+        let cc = CubieCube::from(&[vec![U2], t_perm, vec![U]].concat());
+        let case = recognize_pll(&cc).unwrap();
+        assert_eq!(case.name, "PLL T");
+        assert_eq!(cc.apply_moves(&case.solution()), CubieCube::default());
+        assert_eq!(
+            recognize_pll(&CubieCube::from(&vec![U])).unwrap().post_auf,
+            Some(U3)
+        );
+        assert!(recognize_pll(&CubieCube::from(&vec![F, R, U, R3, U3, F3])).is_err());
+    }
+
+    /// The OLL cases of the seven corner orientations with oriented edges and their OCLL names.
+    const OCLL_SHAPES: [(usize, &str); 7] = [
+        (21, "H"),
+        (22, "Pi"),
+        (23, "U"),
+        (24, "T"),
+        (25, "L"),
+        (26, "AS"),
+        (27, "S"),
+    ];
+
+    /// The OCLL shape of the corner orientation, O for oriented corners.
+    fn ocll_shape(cc: &CubieCube) -> &'static str {
+        if is_corners_oriented(cc) {
+            return "O";
+        }
+        for (oll, shape) in OCLL_SHAPES {
+            let alg = &OLL[oll - 1].1;
+            if (0..4).any(|k| is_corners_oriented(&apply_auf(cc, k).apply_moves(alg))) {
+                return shape;
+            }
+        }
+        unreachable!()
+    }
+
+    /// All permutations of four pieces.
+    fn permutations<T: Copy>(pieces: &[T; 4]) -> Vec<[T; 4]> {
+        let mut perms = Vec::new();
+        for a in 0..4 {
+            for b in (0..4).filter(|b| *b != a) {
+                for c in (0..4).filter(|c| *c != a && *c != b) {
+                    let d = 6 - a - b - c;
+                    perms.push([pieces[a], pieces[b], pieces[c], pieces[d]]);
+                }
+            }
+        }
+        perms
+    }
+
+    #[test]
+    fn test_coll_zbll() {
+        let count = |algorithms: &[(&str, Vec<Move>)], shape| {
+            algorithms
+                .iter()
+                .filter(|(n, _)| n.split(' ').next() == Some(shape))
+                .count()
+        };
+        // the algorithms solve different cases
+        assert_eq!(COLL_CASES.len(), 42);
+        assert_eq!(count(&COLL, "H"), 4);
+        assert_eq!(count(&COLL, "O"), 2);
+        // 472 ZBLL cases and the 21 PLL cases
+        assert_eq!(ZBLL_CASES.len(), 493);
+        assert_eq!(count(&ZBLL, "H"), 40);
+        assert_eq!(count(&ZBLL, "S"), 72);
+
+        // every last layer with oriented edges is a case of the shape of its name
+        for cp in permutations(&[Corner::URF, Corner::UFL, Corner::ULB, Corner::UBR]) {
+            for twist in 0..27u8 {
+                let co = [
+                    twist % 3,
+                    twist / 3 % 3,
+                    twist / 9,
+                    (6 - twist % 3 - twist / 3 % 3 - twist / 9) % 3,
+                ];
+                for ep in permutations(&[Edge::UR, Edge::UF, Edge::UL, Edge::UB]) {
+                    let mut cc = CubieCube::default();
+                    cc.cp[..4].copy_from_slice(&cp);
+                    cc.co[..4].copy_from_slice(&co);
+                    cc.ep[..4].copy_from_slice(&ep);
+                    if cc.corner_parity() != cc.edge_parity() {
+                        continue;
+                    }
+                    let shape = ocll_shape(&cc);
+                    let zbll = recognize_zbll(&cc).unwrap();
+                    for case in [recognize_coll(&cc).unwrap(), zbll.clone()] {
+                        let name: Vec<&str> = case.name.split(' ').collect();
+                        assert!(name[1] == "skip" || name[1] == shape);
+                    }
+                    assert_eq!(cc.apply_moves(&zbll.solution()), CubieCube::default());
+                }
+            }
+        }
+
+        for (name, alg) in COLL.iter() {
+            let case = recognize_coll(&case_of(alg)).unwrap();
+            assert_eq!(case.name, format!("COLL {}", name));
+            assert_eq!((case.pre_auf, case.post_auf), (None, None));
+        }
+        for (name, alg) in ZBLL.iter() {
+            let case = recognize_zbll(&case_of(alg)).unwrap();
+            assert_eq!(case.name, format!("ZBLL {}", name));
+            assert_eq!((case.pre_auf, case.post_auf), (None, None));
+        }
+
+        // Sune with AUFs
+        let cc =
+            CubieCube::from(&[vec![U], OLL[26].1.clone(), vec![U2]].concat()).inverse_cubie_cube();
+        let case = recognize_coll(&cc).unwrap();
+        assert!(case.name.starts_with("COLL S "));
+        assert!(case.pre_auf.is_some());
+        let solved = cc.apply_moves(&case.solution());
+        assert!(is_f2l_solved(&solved) && is_edges_oriented(&solved));
+        assert_eq!(solved.to_bytes()[..8], CubieCube::default().to_bytes()[..8]);
+        let case = recognize_zbll(&cc).unwrap();
+        assert!(case.name.starts_with("ZBLL S "));
+        assert!(case.pre_auf.is_some());
+        assert_eq!(cc.apply_moves(&case.solution()), CubieCube::default());
+        // the edges do not count for COLL
+        assert_eq!(
+            recognize_coll(&case_of(&PLL[16].1)).unwrap().name,
+            "COLL skip"
+        );
+    }
+}
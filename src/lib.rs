@@ -34,6 +34,55 @@ pub mod pruning;
 /// Module for Solver.
 pub mod solver;
 
+/// Module for solving phase 1 (into the subgroup H) and phase 2 (within H) on their own.
+pub mod phases;
+
+/// Module for analyzing scrambles: solution length, phase distances, orientation and blocks.
+pub mod analysis;
+
+/// Module with named cube patterns like checkerboard and superflip.
+pub mod patterns;
+
+/// Module for enumerating many solutions of a cube.
+pub mod enumerate;
+
+/// Module for solving the cross and X-cross of the CFOP method on any face.
+pub mod cross;
+
+/// Module for solving steps of speedsolving methods: EOLine, EOCross, the Roux blocks and F2L pairs.
+pub mod steps;
+
+/// Module for recognizing the OLL, PLL, COLL and ZBLL cases of the last layer.
+pub mod lastlayer;
+
+/// Module for tracing a cube into blindfolded memo with a letter scheme.
+pub mod bld;
+
+/// Module for fewest-moves solving: NISS skeletons, insertions and cancellations.
+pub mod fmc;
+
+/// Module for the extended move notation with wide moves, slice moves and rotations.
+pub mod notation;
+
+/// Module for reconstructing a CFOP solve: step detection, move counts and comparison with optimal steps.
+pub mod reconstruction;
+
+/// Module for cubes with oriented centers like supercubes and picture cubes.
+pub mod supercube;
+
+/// Module for the 2x2x2 pocket cube: facelets, random-state scrambles and an optimal solver.
+pub mod pocket;
+
+/// Module for solve statistics over many seeded random cubes.
+pub mod stats;
+
+/// Module for solving many cubes on a shared pool of worker threads.
+pub mod batch;
+
+/// Module for solving cubes from async code without blocking the runtime.
+#[cfg(feature = "async")]
+pub mod async_solver;
+
 use std::{fs, path::Path};
 use bincode::{
     config::{self, Configuration},
@@ -46,10 +95,24 @@ use crate::moves::Move;
 
 const CONFIG: Configuration = config::standard();
 
+/// There is no file system on wasm32, the data tables are always created in memory there.
+const NO_FILE_SYSTEM: bool = cfg!(target_arch = "wasm32");
+
+/// Create the directory for the data table files.
+fn create_tables_dir() -> Result<(), Error> {
+    if !NO_FILE_SYSTEM {
+        fs::create_dir_all("tables")?;
+    }
+    Ok(())
+}
+
 fn write_table<P, T: Encode>(path: P, table: &T) -> Result<(), Error>
 where
     P: AsRef<Path>,
 {
+    if NO_FILE_SYSTEM {
+        return Ok(());
+    }
     let encoded = encode_to_vec(table, CONFIG)?;
     fs::write(path, encoded)?;
     Ok(())
@@ -3,7 +3,7 @@ use std::{fmt, str::FromStr};
 use self::Move::*;
 use crate::constants::*;
 use crate::cubie::{self, Corner::*, CubieCube, Edge::*};
-use crate::{decode_table, write_table};
+use crate::{create_tables_dir, decode_table, write_table};
 use crate::{error::Error, facelet::Color};
 use serde::{Serialize, Deserialize};
 
@@ -12,7 +12,7 @@ use serde::{Serialize, Deserialize};
 /// $ clockwise, $2 double, $3 counter-clockwise.
 #[rustfmt::skip]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum Move {
     U, U2, U3,
     R, R2, R3,
@@ -106,6 +106,31 @@ impl Move {
     }
 }
 
+/// Merge successive moves of the same face and drop the moves which cancel, e.g. `R U U2 U R'` gives nothing.
+pub fn merge_moves(moves: &[Move]) -> Vec<Move> {
+    let mut merged: Vec<Move> = Vec::with_capacity(moves.len());
+    for m in moves {
+        match merged.last() {
+            Some(last) if last.is_same_layer(*m) => {
+                // quarter turns of both moves
+                let power = (*last as usize % 3 + *m as usize % 3 + 2) % 4;
+                let face = *m as usize / 3;
+                merged.pop();
+                if power != 0 {
+                    merged.push(ALL_MOVES[3 * face + power - 1]);
+                }
+            }
+            _ => merged.push(*m),
+        }
+    }
+    merged
+}
+
+/// Give the inverse of a maneuver: the inverse moves in reverse order.
+pub fn invert_moves(moves: &[Move]) -> Vec<Move> {
+    moves.iter().rev().map(|m| m.get_inverse()).collect()
+}
+
 /// The basic six cube moves described by permutations and changes in orientation.
 /// 
 /// U_MOVE
@@ -231,7 +256,7 @@ pub fn move_twist() -> Result<Vec<u16>, Error> {
 pub fn move_flip() -> Result<Vec<u16>, Error> {
     let mut a = CubieCube::default();
     let bmc = cubie::basic_move_cubes();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/move_flip";
     let flip_move_table = std::fs::read(&fname).unwrap_or("".into());
     let mut flip_move = vec![0; N_FLIP * N_MOVE];
@@ -266,7 +291,7 @@ pub fn move_flip() -> Result<Vec<u16>, Error> {
 pub fn move_slice_sorted() -> Result<Vec<u16>, Error> {
     let mut a = CubieCube::default();
     let bmc = cubie::basic_move_cubes();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/move_slice_sorted";
     let slice_move_table = std::fs::read(&fname).unwrap_or("".into());
     let mut slice_move = vec![0; N_SLICE_SORTED * N_MOVE];
@@ -302,7 +327,7 @@ pub fn move_slice_sorted() -> Result<Vec<u16>, Error> {
 pub fn move_u_edges() -> Result<Vec<u16>, Error> {
     let mut a = CubieCube::default();
     let bmc = cubie::basic_move_cubes();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/move_u_edges";
     let move_u_edges_table = std::fs::read(&fname).unwrap_or("".into());
     let mut u_edges_move = vec![0; N_SLICE_SORTED * N_MOVE];
@@ -338,7 +363,7 @@ pub fn move_u_edges() -> Result<Vec<u16>, Error> {
 pub fn move_d_edges() -> Result<Vec<u16>, Error> {
     let mut a = CubieCube::default();
     let bmc = cubie::basic_move_cubes();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/move_d_edges";
     let move_d_edges_table = std::fs::read(&fname).unwrap_or("".into());
     let mut d_edges_move = vec![0; N_SLICE_SORTED * N_MOVE];
@@ -373,7 +398,7 @@ pub fn move_d_edges() -> Result<Vec<u16>, Error> {
 pub fn move_ud_edges() -> Result<Vec<u16>, Error> {
     let mut a = CubieCube::default();
     let bmc = cubie::basic_move_cubes();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/move_ud_edges";
     let move_ud_edges_table = std::fs::read(&fname).unwrap_or("".into());
     let mut ud_edges_move = vec![0; N_UD_EDGES * N_MOVE];
@@ -417,7 +442,7 @@ pub fn move_ud_edges() -> Result<Vec<u16>, Error> {
 pub fn move_corners() -> Result<Vec<u16>, Error> {
     let mut a = CubieCube::default();
     let bmc = cubie::basic_move_cubes();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/move_corners";
     let move_corners_table = std::fs::read(&fname).unwrap_or("".into());
     let mut corners_move = vec![0; N_CORNERS * N_MOVE];
@@ -451,6 +476,15 @@ pub fn move_corners() -> Result<Vec<u16>, Error> {
 mod test {
     use crate::moves::*;
 
+    #[test]
+    fn test_merge_moves() {
+        assert_eq!(merge_moves(&[R, U, U2, U, R3]), vec![]);
+        assert_eq!(merge_moves(&[R, U, U, F3, F3, D]), vec![R, U2, F2, D]);
+        assert_eq!(merge_moves(&[U, D, U3]), vec![U, D, U3]);
+        assert_eq!(merge_moves(&[B2, B, L3, L3, L3]), vec![B3, L]);
+        assert_eq!(invert_moves(&[R, U2, F3]), vec![F, U2, R3]);
+    }
+
     #[test]
     fn test_move_twist() {
         let move_twist = move_twist().unwrap();
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::constants::{ALL_COLORS, ALL_MOVES};
+use crate::error::Error;
+use crate::facelet::Color;
+use crate::moves::Move;
+
+/// The axes of the cube rotations: x turns like R, y like U and z like F.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// The slice moves: M turns like L, E like D and S like F.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Slice {
+    M,
+    E,
+    S,
+}
+
+/// The metrics to count moves.
+///
+/// * `Htm`: Half turn metric, a face or wide turn is one move, a slice move two and a rotation none.
+/// * `Stm`: Slice turn metric, a face, wide or slice turn is one move and a rotation none.
+/// * `Etm`: Execution turn metric, every move and rotation counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Metric {
+    Htm,
+    Stm,
+    Etm,
+}
+
+/// A move of the notation with wide moves, slice moves and rotations, with 1, 2 or 3 clockwise quarter turns.
+///
+/// A wide move is written `r` or `Rw`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ExtendedMove {
+    Face(Color, u8),
+    Wide(Color, u8),
+    Slice(Slice, u8),
+    Rotation(Axis, u8),
+}
+
+impl FromStr for ExtendedMove {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let base = chars.next().ok_or(Error::InvalidScramble)?;
+        let mut rest = chars.as_str();
+        let wide = rest.starts_with('w');
+        if wide {
+            rest = &rest[1..];
+        }
+        let power = match rest {
+            "" => 1,
+            "2" | "2'" => 2,
+            "'" => 3,
+            _ => return Err(Error::InvalidScramble),
+        };
+        let m = match base {
+            'U' | 'R' | 'F' | 'D' | 'L' | 'B' if wide => {
+                ExtendedMove::Wide(Color::try_from(base)?, power)
+            }
+            _ if wide => return Err(Error::InvalidScramble),
+            'U' | 'R' | 'F' | 'D' | 'L' | 'B' => ExtendedMove::Face(Color::try_from(base)?, power),
+            'u' | 'r' | 'f' | 'd' | 'l' | 'b' => {
+                ExtendedMove::Wide(Color::try_from(base.to_ascii_uppercase())?, power)
+            }
+            'M' => ExtendedMove::Slice(Slice::M, power),
+            'E' => ExtendedMove::Slice(Slice::E, power),
+            'S' => ExtendedMove::Slice(Slice::S, power),
+            'x' => ExtendedMove::Rotation(Axis::X, power),
+            'y' => ExtendedMove::Rotation(Axis::Y, power),
+            'z' => ExtendedMove::Rotation(Axis::Z, power),
+            _ => return Err(Error::InvalidScramble),
+        };
+        Ok(m)
+    }
+}
+
+impl fmt::Display for ExtendedMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, power) = match self {
+            ExtendedMove::Face(c, p) => (c.to_string(), p),
+            ExtendedMove::Wide(c, p) => (c.to_string().to_lowercase(), p),
+            ExtendedMove::Slice(s, p) => (format!("{:?}", s), p),
+            ExtendedMove::Rotation(a, p) => (format!("{:?}", a).to_lowercase(), p),
+        };
+        let suffix = match power {
+            2 => "2",
+            3 => "'",
+            _ => "",
+        };
+        write!(f, "{}{}", name, suffix)
+    }
+}
+
+impl From<Move> for ExtendedMove {
+    fn from(m: Move) -> Self {
+        ExtendedMove::Face(ALL_COLORS[m as usize / 3], m as u8 % 3 + 1)
+    }
+}
+
+impl ExtendedMove {
+    /// The number of moves in the metric.
+    pub fn count(&self, metric: Metric) -> usize {
+        match (self, metric) {
+            (ExtendedMove::Rotation(..), Metric::Htm | Metric::Stm) => 0,
+            (ExtendedMove::Slice(..), Metric::Htm) => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// Parse moves in the extended notation separated by whitespace, e.g. `x' R U r' M2 y`.
+pub fn parse_moves(s: &str) -> Result<Vec<ExtendedMove>, Error> {
+    s.split_whitespace().map(ExtendedMove::from_str).collect()
+}
+
+/// The number of moves of the maneuver in the metric.
+pub fn count_moves(moves: &[ExtendedMove], metric: Metric) -> usize {
+    moves.iter().map(|m| m.count(metric)).sum()
+}
+
+/// The orientation of the cube in the hands: `faces[f]` is the face of the cube with the fixed centers which is at the
+/// face f of the notation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubeOrientation {
+    pub faces: [Color; 6],
+}
+
+impl Default for CubeOrientation {
+    fn default() -> Self {
+        Self {
+            faces: [Color::U, Color::R, Color::F, Color::D, Color::L, Color::B],
+        }
+    }
+}
+
+impl CubeOrientation {
+    /// Rotate the cube by quarter turns around the axis.
+    fn rotate(&mut self, axis: Axis, power: u8) {
+        use Color::*;
+        // the faces which move to U, R, F, D, L, B
+        let cycle = match axis {
+            Axis::X => [F, R, D, B, L, U],
+            Axis::Y => [U, B, R, D, F, L],
+            Axis::Z => [L, U, F, R, D, B],
+        };
+        for _ in 0..power % 4 {
+            let old = self.faces;
+            for (f, from) in cycle.iter().enumerate() {
+                self.faces[f] = old[*from as usize];
+            }
+        }
+    }
+
+    /// The face turn of the notation face by power quarter turns.
+    fn face_turn(&self, face: Color, power: u8) -> Move {
+        ALL_MOVES[3 * self.faces[face as usize] as usize + (power as usize + 3) % 4]
+    }
+
+    /// Apply the move to the orientation and give the face turns of the move for the cube with fixed centers.
+    ///
+    /// A wide move is the turn of the opposite face and a rotation, a slice move the turns of the two outer faces and
+    /// a rotation.
+    pub fn face_turns(&mut self, m: &ExtendedMove) -> Vec<Move> {
+        use Color::*;
+        let (turns, rotation) = match *m {
+            ExtendedMove::Face(face, p) => (vec![(face, p)], None),
+            ExtendedMove::Wide(face, p) => {
+                let (opposite, axis, direction) = match face {
+                    R => (L, Axis::X, 1),
+                    L => (R, Axis::X, 3),
+                    U => (D, Axis::Y, 1),
+                    D => (U, Axis::Y, 3),
+                    F => (B, Axis::Z, 1),
+                    B => (F, Axis::Z, 3),
+                };
+                (vec![(opposite, p)], Some((axis, direction * p)))
+            }
+            ExtendedMove::Slice(slice, p) => match slice {
+                Slice::M => (vec![(R, p), (L, 4 - p)], Some((Axis::X, 3 * p))),
+                Slice::E => (vec![(U, p), (D, 4 - p)], Some((Axis::Y, 3 * p))),
+                Slice::S => (vec![(F, 4 - p), (B, p)], Some((Axis::Z, p))),
+            },
+            ExtendedMove::Rotation(axis, p) => (Vec::new(), Some((axis, p))),
+        };
+        let moves = turns
+            .into_iter()
+            .map(|(face, p)| self.face_turn(face, p))
+            .collect();
+        if let Some((axis, p)) = rotation {
+            self.rotate(axis, p);
+        }
+        moves
+    }
+}
+
+/// Give the face turns of a maneuver in the extended notation for the cube with fixed centers.
+///
+/// The final orientation of the cube is dropped, the face turns give the same cube up to a rotation.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::moves::Move::*;
+/// use kociemba::notation::{parse_moves, to_face_turns};
+///
+/// fn main() {
+///     let moves = parse_moves("r U R' U' M").unwrap();
+///     assert_eq!(to_face_turns(&moves), vec![L, F, R3, F3, R, L3]);
+/// }
+/// ```
+pub fn to_face_turns(moves: &[ExtendedMove]) -> Vec<Move> {
+    let mut orientation = CubeOrientation::default();
+    moves
+        .iter()
+        .flat_map(|m| orientation.face_turns(m))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cubie::CubieCube;
+    use crate::moves::Move::*;
+    use crate::notation::*;
+
+    #[test]
+    fn test_parse_moves() {
+        let moves = parse_moves("R U2' Rw' r2 M' E2 S x' y z2 F'").unwrap();
+        assert_eq!(moves[2], ExtendedMove::Wide(Color::R, 3));
+        assert_eq!(moves[3], ExtendedMove::Wide(Color::R, 2));
+        let s: Vec<String> = moves.iter().map(|m| m.to_string()).collect();
+        assert_eq!(s.join(" "), "R U2 r' r2 M' E2 S x' y z2 F'");
+        assert_eq!(count_moves(&moves, Metric::Htm), 11);
+        assert_eq!(count_moves(&moves, Metric::Stm), 8);
+        assert_eq!(count_moves(&moves, Metric::Etm), 11);
+
+        assert!(parse_moves("R3").is_err());
+        assert!(parse_moves("Mw").is_err());
+        assert!(parse_moves("rw").is_err());
+        assert!(parse_moves("X").is_err());
+    }
+
+    #[test]
+    fn test_face_turns() {
+        // a rotation changes the face turns of the following moves
+        assert_eq!(to_face_turns(&parse_moves("x R U").unwrap()), vec![R, F]);
+        assert_eq!(to_face_turns(&parse_moves("y R U").unwrap()), vec![B, U]);
+        assert_eq!(to_face_turns(&parse_moves("z R U").unwrap()), vec![U, L]);
+        assert_eq!(to_face_turns(&parse_moves("y' z2 F").unwrap()), vec![L]);
+        assert_eq!(
+            to_face_turns(&parse_moves("E S'").unwrap()),
+            vec![U, D3, L, R3]
+        );
+        // a wide move is the same cube as the face turn and the slice move
+        let wide = CubieCube::from(&to_face_turns(&parse_moves("u2 F").unwrap()));
+        let slice = CubieCube::from(&to_face_turns(&parse_moves("U2 E2' F").unwrap()));
+        assert_eq!(wide, slice);
+    }
+}
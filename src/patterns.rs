@@ -0,0 +1,193 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::scramble::scramble_from_str;
+use crate::symmetries::{self, conjugate};
+
+lazy_static! {
+    /// The cubes of [Pattern::ALL].
+    static ref PATTERN_CUBES: Vec<CubieCube> = Pattern::ALL
+        .iter()
+        .map(|p| CubieCube::from(&scramble_from_str(p.maneuver()).unwrap())) // the maneuvers are valid
+        .collect();
+    static ref SYMMETRIES: [CubieCube; 48] = symmetries::sc();
+}
+
+/// Named cube patterns, e.g. as goals of [crate::solver::solve_to_pattern].
+///
+/// # Examples
+/// ```rust
+/// use kociemba::patterns::Pattern;
+///
+/// fn main() {
+///     let pattern: Pattern = "cube-in-cube".parse().unwrap();
+///     println!("{}: {}", pattern, pattern.maneuver());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    Checkerboard,
+    Superflip,
+    CubeInCube,
+    SixSpots,
+    FourSpots,
+    CubeInCubeInCube,
+    PlusMinus,
+    Tetris,
+    Python,
+    Anaconda,
+    Wire,
+    Twister,
+}
+
+impl Pattern {
+    /// All patterns.
+    pub const ALL: [Pattern; 12] = [
+        Pattern::Checkerboard,
+        Pattern::Superflip,
+        Pattern::CubeInCube,
+        Pattern::SixSpots,
+        Pattern::FourSpots,
+        Pattern::CubeInCubeInCube,
+        Pattern::PlusMinus,
+        Pattern::Tetris,
+        Pattern::Python,
+        Pattern::Anaconda,
+        Pattern::Wire,
+        Pattern::Twister,
+    ];
+
+    /// The name of the pattern, as accepted by [Pattern::from_str].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pattern::Checkerboard => "checkerboard",
+            Pattern::Superflip => "superflip",
+            Pattern::CubeInCube => "cube-in-cube",
+            Pattern::SixSpots => "six-spots",
+            Pattern::FourSpots => "four-spots",
+            Pattern::CubeInCubeInCube => "cube-in-cube-in-cube",
+            Pattern::PlusMinus => "plus-minus",
+            Pattern::Tetris => "tetris",
+            Pattern::Python => "python",
+            Pattern::Anaconda => "anaconda",
+            Pattern::Wire => "wire",
+            Pattern::Twister => "twister",
+        }
+    }
+
+    /// A maneuver which creates the pattern from the solved cube.
+    pub fn maneuver(&self) -> &'static str {
+        match self {
+            Pattern::Checkerboard => "U2 D2 F2 B2 L2 R2",
+            Pattern::Superflip => "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2",
+            Pattern::CubeInCube => "F L F U' R U F2 L2 U' L' B D' B' L2 U",
+            Pattern::SixSpots => "U D' R L' F B' U D'",
+            Pattern::FourSpots => "F2 B2 U D' R2 L2 U D'",
+            Pattern::CubeInCubeInCube => "U' L' U' F' R2 B' R F U B2 U B' L U' F U R F'",
+            Pattern::PlusMinus => "U2 R2 L2 U2 R2 L2",
+            Pattern::Tetris => "L R F B U' D' L' R'",
+            Pattern::Python => "F2 R' B' U R' L F' L F' B D' R B L2",
+            Pattern::Anaconda => "L U B' U' R L' B R' F B' D R D' F'",
+            Pattern::Wire => "R L F B R L F B R L F B R2 B2 L2 R2 B2 L2",
+            Pattern::Twister => "F R' U L F' L' F U' R U L' U' L F'",
+        }
+    }
+
+    /// The pattern on the cubie level.
+    pub fn cube(&self) -> CubieCube {
+        PATTERN_CUBES[*self as usize]
+    }
+
+    /// The index of a symmetry s of [symmetries::sc] with s^-1 * cc * s the pattern, None if the cube is not the
+    /// pattern up to symmetry. The pattern may be seen from any side of the cube or in a mirror.
+    pub fn symmetry(&self, cc: &CubieCube) -> Option<usize> {
+        let cube = self.cube();
+        SYMMETRIES.iter().position(|s| conjugate(s, cc) == cube)
+    }
+}
+
+/// Recognize the pattern of a cube up to the symmetries of the cube, None if it is none of [Pattern::ALL].
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::patterns::{recognize_pattern, Pattern};
+/// use kociemba::scramble::scramble_from_str;
+///
+/// fn main() {
+///     // the six spots seen from the front
+///     let cc = CubieCube::from(&scramble_from_str("F B' R L' D U' F B'").unwrap());
+///     assert_eq!(recognize_pattern(&cc), Some(Pattern::SixSpots));
+/// }
+/// ```
+pub fn recognize_pattern(cc: &CubieCube) -> Option<Pattern> {
+    Pattern::ALL.into_iter().find(|p| p.symmetry(cc).is_some())
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['_', ' '], "-");
+        Pattern::ALL
+            .into_iter()
+            .find(|p| p.name() == name)
+            .ok_or(Error::InvalidPattern)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::facelet::FaceCube;
+    use crate::patterns::*;
+
+    #[test]
+    fn test_pattern_cube() {
+        for p in Pattern::ALL {
+            assert!(p.cube().verify().unwrap());
+            assert_ne!(p.cube(), CubieCube::default());
+        }
+        let fc = FaceCube::try_from(&Pattern::Checkerboard.cube()).unwrap();
+        assert_eq!(
+            fc.to_string(),
+            "UDUDUDUDURLRLRLRLRFBFBFBFBFDUDUDUDUDLRLRLRLRLBFBFBFBFB"
+        );
+        let superflip = Pattern::Superflip.cube();
+        assert_eq!(superflip.cp, CubieCube::default().cp);
+        assert_eq!(superflip.ep, CubieCube::default().ep);
+        assert!(superflip.eo.iter().all(|o| *o == 1));
+    }
+
+    #[test]
+    fn test_pattern_from_str() {
+        for p in Pattern::ALL {
+            assert_eq!(p.to_string().parse::<Pattern>().unwrap(), p);
+        }
+        assert_eq!("Cube In Cube".parse::<Pattern>().unwrap(), Pattern::CubeInCube);
+        assert!("checker".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn test_recognize_pattern() {
+        for p in Pattern::ALL {
+            assert_eq!(recognize_pattern(&p.cube()), Some(p));
+            assert_eq!(p.symmetry(&p.cube()), Some(0));
+        }
+        // the checkerboard of the mirrored maneuver and the tetris pattern seen from the right
+        let cc = CubieCube::from(&scramble_from_str("R2 L2 U2 D2 F2 B2").unwrap());
+        assert_eq!(recognize_pattern(&cc), Some(Pattern::Checkerboard));
+        let cc = CubieCube::from(&scramble_from_str("B F R L U' D' B' F'").unwrap());
+        assert_eq!(recognize_pattern(&cc), Some(Pattern::Tetris));
+        assert_eq!(recognize_pattern(&CubieCube::default()), None);
+        let cc = CubieCube::from(&scramble_from_str("R U").unwrap());
+        assert_eq!(recognize_pattern(&cc), None);
+    }
+}
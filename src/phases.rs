@@ -0,0 +1,352 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::max;
+
+use crate::constants::*;
+use crate::coord::CoordCube;
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::moves::Move;
+use crate::solver::{solver_tables, SolverTables};
+
+/// The moves of the subgroup H = <U, D, R2, F2, L2, B2>.
+pub const PHASE2_MOVES: [Move; 10] = [
+    Move::U,
+    Move::U2,
+    Move::U3,
+    Move::R2,
+    Move::F2,
+    Move::D,
+    Move::D2,
+    Move::D3,
+    Move::L2,
+    Move::B2,
+];
+
+/// Shortest maneuvers of a phase:
+/// * distance: the length of the shortest maneuvers.
+/// * maneuvers: shortest maneuvers, at most the requested number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseSolutions {
+    pub distance: u16,
+    pub maneuvers: Vec<Vec<Move>>,
+}
+
+/// Distances of a cube from the pruning tables:
+/// * phase1: exact number of moves into the subgroup H, a lower bound for the number of moves to solve the cube.
+/// * phase2: lower bound for the number of moves of H which solve the cube, None if it is not in H.
+/// * cornslice: exact number of moves of H which solve corners and UD-slice edges, None if the cube is not in H.
+///
+/// See [SolverTables::phase1_distance], [SolverTables::phase2_bound] and [SolverTables::cornslice_bound].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Distances {
+    pub phase1: u16,
+    pub phase2: Option<u16>,
+    pub cornslice: Option<u16>,
+}
+
+/// Give the [Distances] of the cube cc.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::phases::distances;
+/// use kociemba::scramble::scramble_from_str;
+///
+/// fn main() {
+///     let cc = CubieCube::from(&scramble_from_str("R U R' U'").unwrap());
+///     println!("{:?}", distances(&cc).unwrap());
+/// }
+/// ```
+pub fn distances(cc: &CubieCube) -> Result<Distances, Error> {
+    cc.verify()?;
+    let tables = solver_tables();
+    let co = CoordCube::from_cubie(cc, &tables.sy)?;
+    Ok(coord_distances(&co))
+}
+
+/// Give the [Distances] of the cube co.
+pub fn coord_distances(co: &CoordCube) -> Distances {
+    let tables = solver_tables();
+    Distances {
+        phase1: tables.phase1_distance(co),
+        phase2: tables.phase2_bound(co),
+        cornslice: tables.cornslice_bound(co),
+    }
+}
+
+/// Check if the cube cc is in the subgroup H = <U, D, R2, F2, L2, B2>: no twisted corners, no flipped edges
+/// and the UD-slice edges in the UD-slice.
+pub fn in_subgroup_h(cc: &CubieCube) -> bool {
+    cc.get_twist() == 0 && cc.get_flip() == 0 && cc.get_slice_sorted() < N_PERM_4 as u16
+}
+
+/// Find the shortest maneuvers which bring the cube cc into the subgroup H (phase 1 of the two-phase algorithm).
+///
+/// Gives at most `max_solutions` maneuvers, all of them with length `distance`. The phase 1 pruning table stores
+/// the exact distance to H, so only moves which reduce it are tried. With `max_solutions` 0 only the distance is given.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::phases::solve_phase1;
+/// use kociemba::scramble::scramble_from_str;
+///
+/// fn main() {
+///     let cc = CubieCube::from(&scramble_from_str("R U F D' L2 B").unwrap());
+///     let result = solve_phase1(&cc, 5).unwrap();
+///     println!("{} {:?}", result.distance, result.maneuvers);
+/// }
+/// ```
+pub fn solve_phase1(cc: &CubieCube, max_solutions: usize) -> Result<PhaseSolutions, Error> {
+    cc.verify()?;
+    let tables = solver_tables();
+    let (flip, twist, slice_sorted) = (cc.get_flip(), cc.get_twist(), cc.get_slice_sorted());
+    let distance = tables.phase1_depth(flip, twist, slice_sorted) as u16;
+    let mut search = PhaseSearch::new(tables, max_solutions);
+    search.phase1(flip, twist, slice_sorted, distance);
+    search.maneuvers.truncate(max_solutions);
+    Ok(PhaseSolutions {
+        distance,
+        maneuvers: search.maneuvers,
+    })
+}
+
+/// Find the shortest maneuvers which solve the cube cc in the subgroup H using only moves of H
+/// (phase 2 of the two-phase algorithm).
+///
+/// Gives [Error::NotInSubgroupH] if cc is not in H, see [in_subgroup_h]. The search deepens iteratively from the
+/// lower bound given by the phase 2 pruning tables, so the distance is exact. With `max_solutions` 0 only the distance
+/// is given.
+pub fn solve_phase2(cc: &CubieCube, max_solutions: usize) -> Result<PhaseSolutions, Error> {
+    cc.verify()?;
+    if !in_subgroup_h(cc) {
+        return Err(Error::NotInSubgroupH);
+    }
+    let tables = solver_tables();
+    let (corners, ud_edges, slice_sorted) =
+        (cc.get_corners(), cc.get_ud_edges(), cc.get_slice_sorted());
+    let dist = tables.phase2_depth(corners, ud_edges);
+    let lower = max(
+        dist,
+        tables.pr.cornslice_depth[24 * corners as usize + slice_sorted as usize],
+    );
+    // at least one maneuver is needed to know the distance
+    let mut search = PhaseSearch::new(tables, max(max_solutions, 1));
+    let mut distance = lower;
+    // the diameter of H is 18 moves
+    while distance <= 18 {
+        search.phase2(corners, ud_edges, slice_sorted, dist, distance);
+        if !search.maneuvers.is_empty() {
+            break;
+        }
+        distance += 1;
+    }
+    search.maneuvers.truncate(max_solutions);
+    Ok(PhaseSolutions {
+        distance,
+        maneuvers: search.maneuvers,
+    })
+}
+
+/// Depth first search of the shortest maneuvers of a phase.
+struct PhaseSearch<'a> {
+    tables: &'a SolverTables,
+    max_solutions: usize,
+    sofar: Vec<Move>,
+    maneuvers: Vec<Vec<Move>>,
+}
+
+impl<'a> PhaseSearch<'a> {
+    fn new(tables: &'a SolverTables, max_solutions: usize) -> Self {
+        Self {
+            tables,
+            max_solutions,
+            sofar: Vec::new(),
+            maneuvers: Vec::new(),
+        }
+    }
+
+    /// Check if the move m may follow the moves so far: not on the same face, opposite faces in one order only.
+    fn allowed(&self, m: Move) -> bool {
+        match self.sofar.last() {
+            Some(last) => ![0, 3].contains(&(*last as i8 / 3 - m as i8 / 3)),
+            None => true,
+        }
+    }
+
+    fn done(&self) -> bool {
+        self.maneuvers.len() >= self.max_solutions
+    }
+
+    /// Search all maneuvers into H, dist is the exact distance of the position to H.
+    fn phase1(&mut self, flip: u16, twist: u16, slice_sorted: u16, dist: u16) {
+        if dist == 0 {
+            self.maneuvers.push(self.sofar.clone());
+            return;
+        }
+        let t = self.tables;
+        for m in ALL_MOVES {
+            if self.done() {
+                return;
+            }
+            if !self.allowed(m) {
+                continue;
+            }
+            let flip_new = t.mv.flip_move[N_MOVE * flip as usize + m as usize];
+            let twist_new = t.mv.twist_move[N_MOVE * twist as usize + m as usize];
+            let slice_sorted_new =
+                t.mv.slice_sorted_move[N_MOVE * slice_sorted as usize + m as usize];
+            let flipslice = N_FLIP * (slice_sorted_new as usize / N_PERM_4) + flip_new as usize;
+            let classidx = t.sy.flipslice_classidx[flipslice];
+            let sym = t.sy.flipslice_sym[flipslice];
+            let dist_new_mod3 = t.pr.get_flipslice_twist_depth3(
+                N_TWIST * classidx as usize
+                    + t.sy.twist_conj[((twist_new as usize) << 4) + sym as usize] as usize,
+            );
+            if t.pr.distance[3 * dist as usize + dist_new_mod3 as usize] + 1 != dist {
+                continue; // the move does not bring the cube closer to H
+            }
+            self.sofar.push(m);
+            self.phase1(flip_new, twist_new, slice_sorted_new, dist - 1);
+            self.sofar.pop();
+        }
+    }
+
+    /// Search all maneuvers of length togo which solve a cube in H.
+    ///
+    /// dist is the distance of corners and ud_edges in the phase 2 pruning table, 11 if it is not in the table.
+    fn phase2(&mut self, corners: u16, ud_edges: u16, slice_sorted: u16, dist: u16, togo: u16) {
+        if togo == 0 {
+            // the pruning below guarantees the cube is solved
+            self.maneuvers.push(self.sofar.clone());
+            return;
+        }
+        let t = self.tables;
+        for m in PHASE2_MOVES {
+            if self.done() {
+                return;
+            }
+            if !self.allowed(m) {
+                continue;
+            }
+            let corners_new = t.mv.corners_move[N_MOVE * corners as usize + m as usize];
+            let ud_edges_new = t.mv.ud_edges_move[N_MOVE * ud_edges as usize + m as usize];
+            let slice_sorted_new =
+                t.mv.slice_sorted_move[N_MOVE * slice_sorted as usize + m as usize];
+            let classidx = t.sy.corner_classidx[corners_new as usize];
+            let sym = t.sy.corner_sym[corners_new as usize];
+            let dist_new_mod3 = t.pr.get_corners_ud_edges_depth3(
+                N_UD_EDGES * classidx as usize
+                    + t.sy.ud_edges_conj[((ud_edges_new as usize) << 4) + sym as usize] as usize,
+            );
+            let dist_new = match (dist_new_mod3, dist) {
+                (3, _) => 11,  // not in the table, at least 11 moves
+                (_, 11) => 10, // from at least 11 moves into the table
+                _ => t.pr.distance[3 * dist as usize + dist_new_mod3 as usize],
+            };
+            let cornslice =
+                t.pr.cornslice_depth[N_PERM_4 * corners_new as usize + slice_sorted_new as usize];
+            if max(dist_new, cornslice) >= togo {
+                continue; // impossible to solve the cube in togo - 1 moves
+            }
+            self.sofar.push(m);
+            self.phase2(
+                corners_new,
+                ud_edges_new,
+                slice_sorted_new,
+                dist_new,
+                togo - 1,
+            );
+            self.sofar.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::moves::Move::*;
+    use crate::phases::*;
+    use crate::scramble::scramble_from_str;
+
+    #[test]
+    fn test_solve_phase1() {
+        let cc = CubieCube::from(&scramble_from_str("R U F D' L2 B").unwrap());
+        let result = solve_phase1(&cc, 1000).unwrap();
+        assert!(result.distance <= 6);
+        assert!(!result.maneuvers.is_empty());
+        for man in &result.maneuvers {
+            assert_eq!(man.len(), result.distance as usize);
+            assert!(in_subgroup_h(&cc.apply_moves(man)));
+        }
+        assert_eq!(solve_phase1(&cc, 1).unwrap().maneuvers.len(), 1);
+
+        let result = solve_phase1(&CubieCube::from(&vec![R, F]), 10).unwrap();
+        assert_eq!(result.distance, 2);
+        assert!(result.maneuvers.contains(&vec![F3, R3]));
+        let result = solve_phase1(&CubieCube::from(&vec![U, R2]), 10).unwrap();
+        assert_eq!(result.distance, 0);
+        assert_eq!(result.maneuvers, vec![Vec::<Move>::new()]);
+
+        // only the distance
+        for cc in [cc, CubieCube::from(&vec![U, R2])] {
+            let result = solve_phase1(&cc, 0).unwrap();
+            assert_eq!(result.distance, solve_phase1(&cc, 1).unwrap().distance);
+            assert!(result.maneuvers.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_distances() {
+        let cc = CubieCube::from(&scramble_from_str("R U R' U'").unwrap());
+        let d = distances(&cc).unwrap();
+        assert_eq!(d.phase1, solve_phase1(&cc, 1).unwrap().distance);
+        assert_eq!(d.phase2, None);
+        assert_eq!(d.cornslice, None);
+
+        let cc = CubieCube::from(&vec![U, R2, F2, D3, L2, B2, U2, R2]);
+        let d = distances(&cc).unwrap();
+        assert_eq!(d.phase1, 0);
+        let distance = solve_phase2(&cc, 1).unwrap().distance;
+        assert!(d.phase2.unwrap() <= distance);
+        assert!(d.cornslice.unwrap() <= distance);
+
+        let d = distances(&CubieCube::default()).unwrap();
+        assert_eq!((d.phase1, d.phase2, d.cornslice), (0, Some(0), Some(0)));
+
+        let tables = solver_tables();
+        let mut co = CoordCube::from_cubie(&CubieCube::default(), &tables.sy).unwrap();
+        co.phase1_move(R, &tables.mv, &tables.sy);
+        assert_eq!(coord_distances(&co).phase1, 1);
+    }
+
+    #[test]
+    fn test_solve_phase2() {
+        let cc = CubieCube::from(&vec![U, R2, F2, D3, L2, B2, U2, R2]);
+        let result = solve_phase2(&cc, 1000).unwrap();
+        assert_eq!(result.distance, 8);
+        assert!(result
+            .maneuvers
+            .contains(&vec![R2, U2, B2, L2, D, F2, R2, U3]));
+        for man in &result.maneuvers {
+            assert_eq!(cc.apply_moves(man), CubieCube::default());
+            assert!(man.iter().all(|m| PHASE2_MOVES.contains(m)));
+        }
+
+        // a position in H which needs more moves than the phase 2 pruning table covers
+        let cc =
+            CubieCube::from(&scramble_from_str("U D R2 L2 F2 B2 U' D' R2 L2 F2 B2 U2 R2").unwrap());
+        let result = solve_phase2(&cc, 1).unwrap();
+        assert_eq!(cc.apply_moves(&result.maneuvers[0]), CubieCube::default());
+        assert_eq!(result.maneuvers[0].len(), result.distance as usize);
+
+        // only the distance
+        let result = solve_phase2(&cc, 0).unwrap();
+        assert_eq!(result.distance, solve_phase2(&cc, 1).unwrap().distance);
+        assert!(result.maneuvers.is_empty());
+
+        assert!(matches!(
+            solve_phase2(&CubieCube::from(&vec![R]), 1),
+            Err(Error::NotInSubgroupH)
+        ));
+    }
+}
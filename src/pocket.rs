@@ -0,0 +1,363 @@
+use rand::prelude::*;
+use std::fmt;
+
+use crate::constants::*;
+use crate::cubie::{Corner, Corner::*, CubieCube};
+use crate::error::Error;
+use crate::facelet::{Color, Facelet, CORNER_COLOR, CORNER_FACELET};
+use crate::moves::{self, invert_moves, Move, Move::*};
+use crate::symmetries::{self, conjugate, conjugate_move};
+use crate::{create_tables_dir, decode_table, write_table};
+
+/// Number of 2x2x2 positions up to rotations of the whole cube: with the DBL corner fixed the other 7 corners
+/// have 7! = 5040 permutations and 3^6 = 729 twists.
+pub const N_POCKET: usize = 5040 * 729;
+
+/// The moves of the pocket cube solver, they keep the DBL corner in place.
+pub const POCKET_MOVES: [Move; 9] = [U, U2, U3, R, R2, R3, F, F2, F3];
+
+/// Index of the symmetry (rotation by 90° around the UD axis) which maps positions with fixed DBL corner to
+/// positions with fixed DRB corner. The corners and twist coordinates are small there.
+const TO_DRB: usize = 2;
+
+const N_TWIST_POCKET: usize = 729;
+
+lazy_static! {
+    /// The distances of all positions, created/loaded on first use.
+    static ref POCKET_TABLE: Vec<u8> = pocket_distance_table().unwrap();
+}
+
+/// A 2x2x2 pocket cube, given by the permutation and orientation of the corners like in [CubieCube].
+///
+/// Without centers a pocket cube is also solved if it is rotated as a whole.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PocketCube {
+    pub cp: [Corner; 8],
+    pub co: [u8; 8],
+}
+
+impl Default for PocketCube {
+    fn default() -> Self {
+        Self::from(&CubieCube::default())
+    }
+}
+
+impl From<&CubieCube> for PocketCube {
+    /// The corners of the 3x3 cube.
+    fn from(cc: &CubieCube) -> Self {
+        Self {
+            cp: cc.cp,
+            co: cc.co,
+        }
+    }
+}
+
+impl From<&Vec<Move>> for PocketCube {
+    fn from(moves: &Vec<Move>) -> Self {
+        PocketCube::default().apply_moves(moves)
+    }
+}
+
+/// Facelet of a 2x2x2 cube for the 3x3 corner facelet f, the 4 facelets of each face in reading order.
+fn pocket_facelet(f: Facelet) -> usize {
+    let f = f as usize;
+    4 * (f / 9) + [0, 0, 1, 0, 0, 0, 2, 0, 3][f % 9]
+}
+
+impl TryFrom<&str> for PocketCube {
+    type Error = Error;
+
+    /// Parse a string of 24 facelets in the order U, R, F, D, L, B like the facelet string of the 3x3 cube, e.g.
+    /// `UUUURRRRFFFFDDDDLLLLBBBB` for the solved cube.
+    fn try_from(cube_string: &str) -> Result<Self, Self::Error> {
+        if cube_string.chars().count() != 24 {
+            return Err(Error::InvalidFaceletString);
+        }
+        let colors = cube_string
+            .chars()
+            .map(Color::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut pc = PocketCube::default();
+        for (i, facelets) in CORNER_FACELET.iter().enumerate() {
+            let col = facelets.map(|f| colors[pocket_facelet(f)]);
+            // the orientation is the index of the U or D sticker
+            let ori = col
+                .iter()
+                .position(|c| *c == Color::U || *c == Color::D)
+                .ok_or(Error::InvalidFaceletValue)?;
+            let j = (0..8)
+                .find(|j| (0..3).all(|k| CORNER_COLOR[*j][k] == col[(ori + k) % 3]))
+                .ok_or(Error::InvalidFaceletValue)?;
+            pc.cp[i] = Corner::try_from(j as u8)?;
+            pc.co[i] = ori as u8;
+        }
+        if !pc.verify() {
+            return Err(Error::InvalidFaceletValue);
+        }
+        Ok(pc)
+    }
+}
+
+impl fmt::Display for PocketCube {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut colors = [Color::U; 24];
+        for (i, facelets) in CORNER_FACELET.iter().enumerate() {
+            let corner = self.cp[i] as usize;
+            for (j, fc) in facelets.iter().enumerate() {
+                colors[pocket_facelet(*fc)] =
+                    CORNER_COLOR[corner][(j + 3 - self.co[i] as usize) % 3];
+            }
+        }
+        let s = colors
+            .iter()
+            .fold(String::new(), |acc, c| format!("{acc}{c}"));
+        write!(f, "{s}")
+    }
+}
+
+impl PocketCube {
+    /// The pocket cube as 3x3 cube with solved edges.
+    pub fn cubie(&self) -> CubieCube {
+        CubieCube {
+            cp: self.cp,
+            co: self.co,
+            ..Default::default()
+        }
+    }
+
+    /// Applies a move to the current state.
+    pub fn apply_move(self, m: Move) -> Self {
+        Self::from(&self.cubie().apply_move(m))
+    }
+
+    /// Applies the sequence of moves to the current state.
+    pub fn apply_moves(&self, moves: &[Move]) -> Self {
+        Self::from(&self.cubie().apply_moves(moves))
+    }
+
+    /// Check that every corner appears once and the total twist is a multiple of 3.
+    ///
+    /// Unlike on the 3x3 cube every corner permutation is possible.
+    pub fn verify(&self) -> bool {
+        let mut count = [0; 8];
+        for c in self.cp {
+            count[c as usize] += 1;
+        }
+        count.iter().all(|n| *n == 1)
+            && self.co.iter().all(|o| *o < 3)
+            && self.co.iter().map(|o| *o as u32).sum::<u32>() % 3 == 0
+    }
+
+    /// Check if every face has one color, the cube may be rotated.
+    pub fn is_solved(&self) -> bool {
+        self.normalize().1 == CubieCube::default()
+    }
+
+    /// Generate a random state. The probability is the same for all possible states.
+    pub fn randomize(&mut self) {
+        self.randomize_with(&mut thread_rng());
+    }
+
+    /// Generate a random state with the random number generator rng, the DBL corner is not moved.
+    pub fn randomize_with<R: Rng>(&mut self, rng: &mut R) {
+        let positions: Vec<Corner> = ALL_CORNERS.into_iter().filter(|c| *c != DBL).collect();
+        let mut pieces = positions.clone();
+        pieces.shuffle(rng);
+        let mut twist = 0;
+        for (k, (pos, piece)) in positions.iter().zip(pieces).enumerate() {
+            self.cp[*pos as usize] = piece;
+            self.co[*pos as usize] = if k < 6 {
+                rng.gen_range(0..3)
+            } else {
+                (3 - twist % 3) % 3
+            };
+            twist += self.co[*pos as usize];
+        }
+        self.cp[DBL as usize] = DBL;
+        self.co[DBL as usize] = 0;
+    }
+
+    /// Rotate the cube as a whole so that the DBL corner is in place, give the rotation and the rotated cube.
+    fn normalize(&self) -> (CubieCube, CubieCube) {
+        let sc = symmetries::sc();
+        // the symmetries with even index are rotations, the odd ones are reflections
+        for s in sc.iter().step_by(2) {
+            let mut cc = self.cubie();
+            cc.corner_multiply(*s);
+            if cc.cp[DBL as usize] == DBL && cc.co[DBL as usize] == 0 {
+                return (*s, cc);
+            }
+        }
+        unreachable!("one rotation puts the DBL corner in place")
+    }
+}
+
+/// Index of a position with fixed DBL corner in the distance table.
+fn pocket_index(cc: &CubieCube) -> usize {
+    // with the DRB corner in place corners < 5040 and the twist of the DBL corner is given by the others
+    let cc = conjugate(&symmetries::sc()[TO_DRB], cc);
+    cc.get_corners() as usize * N_TWIST_POCKET + cc.get_twist() as usize / 3
+}
+
+/// The twist coordinate of the compact twist t with the DRB corner not twisted.
+fn full_twist(t: usize) -> usize {
+    let mut digits = 0;
+    let mut x = t;
+    for _ in 0..6 {
+        digits += x % 3;
+        x /= 3;
+    }
+    3 * t + (3 - digits % 3) % 3
+}
+
+/// Distance table for all pocket cube positions with the moves [POCKET_MOVES], created by a breadth-first search.
+///
+/// The maximal distance is 11 moves.
+pub fn pocket_distance_table() -> Result<Vec<u8>, Error> {
+    create_tables_dir()?;
+    let fname = "tables/pocket_distance";
+    let pocket_table = std::fs::read(fname).unwrap_or("".into());
+    if !pocket_table.is_empty() {
+        return decode_table(&pocket_table);
+    }
+    println!("Creating {} table...", fname);
+    let corners_move = moves::move_corners()?;
+    let twist_move = moves::move_twist()?;
+    let s = symmetries::sc()[TO_DRB];
+    let frame_moves = POCKET_MOVES.map(|m| conjugate_move(&s, m) as usize);
+    let mut table = vec![u8::MAX; N_POCKET];
+    table[0] = 0;
+    let mut frontier = vec![0];
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        let mut next = Vec::new();
+        for idx in frontier {
+            let corners = idx / N_TWIST_POCKET;
+            let twist = full_twist(idx % N_TWIST_POCKET);
+            for m in frame_moves {
+                let corners1 = corners_move[N_MOVE * corners + m] as usize;
+                let twist1 = twist_move[N_MOVE * twist + m] as usize / 3;
+                let idx1 = corners1 * N_TWIST_POCKET + twist1;
+                if table[idx1] == u8::MAX {
+                    table[idx1] = depth + 1;
+                    next.push(idx1);
+                }
+            }
+        }
+        frontier = next;
+        depth += 1;
+    }
+    write_table(fname, &table)?;
+    Ok(table)
+}
+
+/// Give the optimal number of moves (half turn metric) to solve the pocket cube.
+pub fn distance(pc: &PocketCube) -> Result<u8, Error> {
+    if !pc.verify() {
+        return Err(Error::InvalidCubieValue);
+    }
+    Ok(POCKET_TABLE[pocket_index(&pc.normalize().1)])
+}
+
+/// Give an optimal solution (half turn metric) of the pocket cube.
+///
+/// The solution is given for the cube as it is held, after the solution every face has one color.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::pocket::{solve, PocketCube};
+///
+/// fn main() {
+///     let pc = PocketCube::try_from("DUUDLLFDBFURLUDRRRLBFBFB").unwrap();
+///     let solution = solve(&pc).unwrap();
+///     assert!(pc.apply_moves(&solution).is_solved());
+/// }
+/// ```
+pub fn solve(pc: &PocketCube) -> Result<Vec<Move>, Error> {
+    if !pc.verify() {
+        return Err(Error::InvalidCubieValue);
+    }
+    let (s, mut cc) = pc.normalize();
+    let mut depth = POCKET_TABLE[pocket_index(&cc)];
+    let mut solution = Vec::new();
+    while depth > 0 {
+        for m in POCKET_MOVES {
+            let cc1 = cc.apply_move(m);
+            if POCKET_TABLE[pocket_index(&cc1)] == depth - 1 {
+                solution.push(m);
+                cc = cc1;
+                depth -= 1;
+                break;
+            }
+        }
+    }
+    // a move m on the rotated cube is the move s * m * s^-1 on the cube as it is held
+    let s_inv = s.inverse_cubie_cube();
+    Ok(solution
+        .into_iter()
+        .map(|m| conjugate_move(&s_inv, m))
+        .collect())
+}
+
+/// Generate a random-state scramble: the inverse of an optimal solution of a random position.
+pub fn random_scramble() -> Vec<Move> {
+    random_scramble_with(&mut thread_rng())
+}
+
+/// Generate a random-state scramble with the random number generator rng.
+pub fn random_scramble_with<R: Rng>(rng: &mut R) -> Vec<Move> {
+    let mut pc = PocketCube::default();
+    pc.randomize_with(rng);
+    let solution = solve(&pc).unwrap(); // random states are valid
+    invert_moves(&solution)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::pocket::*;
+    use crate::scramble::scramble_from_str;
+
+    #[test]
+    fn test_pocket_table() {
+        let mut count = [0; 12];
+        for d in POCKET_TABLE.iter() {
+            count[*d as usize] += 1;
+        }
+        assert_eq!(
+            count,
+            [1, 9, 54, 321, 1847, 9992, 50136, 227536, 870072, 1887748, 623800, 2644]
+        );
+    }
+
+    #[test]
+    fn test_pocket_facelets() {
+        let pc = PocketCube::default();
+        assert_eq!(pc.to_string(), "UUUURRRRFFFFDDDDLLLLBBBB");
+        let pc = PocketCube::from(&scramble_from_str("R U F' L2 D B").unwrap());
+        assert_eq!(PocketCube::try_from(pc.to_string().as_str()).unwrap(), pc);
+        assert!(PocketCube::try_from("UUUURRRRFFFFDDDDLLLLBBB").is_err());
+        assert!(PocketCube::try_from("UUUURRRRFFFFDDDDLLLLBBBU").is_err());
+    }
+
+    #[test]
+    fn test_pocket_solve() {
+        let pc = PocketCube::from(&scramble_from_str("R U R' U'").unwrap());
+        assert_eq!(distance(&pc).unwrap(), 4);
+        assert_eq!(solve(&pc).unwrap(), vec![U, R, U3, R3]);
+        // moves of the D, L and B layers are rotations of U, R and F moves
+        let pc = PocketCube::from(&scramble_from_str("L D2 B'").unwrap());
+        assert_eq!(distance(&pc).unwrap(), 3);
+        assert!(pc.apply_moves(&solve(&pc).unwrap()).is_solved());
+        assert!(PocketCube::from(&vec![R, L3]).is_solved());
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..20 {
+            let scramble = random_scramble_with(&mut rng);
+            assert!(scramble.len() <= 11);
+            let pc = PocketCube::from(&scramble);
+            let solution = solve(&pc).unwrap();
+            assert_eq!(solution.len(), scramble.len());
+            assert!(pc.apply_moves(&solution).is_solved());
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::cubie::CubieCube;
 use crate::moves::{Move, MoveTables};
 use crate::symmetries::SymmetriesTables;
 use crate::error::Error;
-use crate::{decode_table, write_table};
+use crate::{create_tables_dir, decode_table, write_table};
 
 /// The pruning tables cut the search tree during the search.
 /// 
@@ -76,7 +76,7 @@ impl PrunningTables {
     /// Create/load the flipslice_twist_depth3 pruning table for phase 1.
     pub fn create_phase1_prun_table(&mut self, sy: &SymmetriesTables, mv: &MoveTables) -> Result<(), Error> {
         let total: usize = N_FLIPSLICE_CLASS * N_TWIST;
-        std::fs::create_dir_all("tables")?;
+        create_tables_dir()?;
         let fname = "tables/phase1_prun";
         let phase1_prun_table = std::fs::read(&fname).unwrap_or("".into());
 
@@ -243,7 +243,7 @@ impl PrunningTables {
     pub fn create_phase2_prun_table(&mut self, sy: &SymmetriesTables, mv: &MoveTables) -> Result<(), Error> {
         let total = N_CORNERS_CLASS * N_UD_EDGES;
         let fname = "tables/phase2_prun";
-        std::fs::create_dir_all("tables")?;
+        create_tables_dir()?;
         let phase2_prun_table = std::fs::read(&fname).unwrap_or("".into());
         let corner_classidx = &sy.corner_classidx;
         let corner_sym = &sy.corner_sym;
@@ -386,7 +386,7 @@ impl PrunningTables {
     /// With this table we do a fast precheck at the beginning of phase 2.
     pub fn create_phase2_cornsliceprun_table(&mut self, mv: &MoveTables) -> Result<(), Error> {
         let fname = "tables/phase2_cornsliceprun";
-        std::fs::create_dir_all("tables")?;
+        create_tables_dir()?;
         let phase2_cornsliceprun_table = std::fs::read(&fname).unwrap_or("".into());
         let corners_move = &mv.corners_move;
         let slice_sorted_move = &mv.slice_sorted_move;
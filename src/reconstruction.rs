@@ -0,0 +1,387 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::constants::*;
+use crate::cross::{is_cross_solved, rotation_to_d, solve_cross, D_SLOTS};
+use crate::cubie::{CubieCube, Edge::*};
+use crate::error::Error;
+use crate::facelet::Color;
+use crate::lastlayer::{recognize_oll, recognize_pll};
+use crate::notation::{
+    count_moves, parse_moves, to_face_turns, CubeOrientation, ExtendedMove, Metric,
+};
+use crate::steps::{StepGoal, StepSolver};
+use crate::symmetries::conjugate;
+
+/// A step of a reconstructed CFOP solve.
+///
+/// * `name`: `Cross`, `F2L 1` to `F2L 4`, `OLL` or `PLL`.
+/// * `moves`: The moves of the step, with the rotations before the first turn of the step.
+/// * `htm`, `stm`, `etm`: The number of moves in the half turn, slice turn and execution turn metric.
+/// * `case`: The recognized case for OLL and PLL, e.g. `OLL 27` or `PLL skip`.
+/// * `reference`: The number of moves of the optimal solution of the pieces solved by the step, OLL and PLL keep the
+///   first two layers. None for an unfinished step.
+/// * `algorithm`: The number of moves of the algorithm of the database with the AUFs for OLL and PLL, it does not
+///   count for the excess.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepReport {
+    pub name: String,
+    pub moves: Vec<ExtendedMove>,
+    pub htm: usize,
+    pub stm: usize,
+    pub etm: usize,
+    pub case: Option<String>,
+    pub reference: Option<usize>,
+    pub algorithm: Option<usize>,
+}
+
+impl StepReport {
+    /// The number of moves in the half turn metric more than the reference, 0 if the step is shorter.
+    pub fn excess(&self) -> usize {
+        self.reference.map_or(0, |r| self.htm.saturating_sub(r))
+    }
+}
+
+impl fmt::Display for StepReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let moves: Vec<String> = self.moves.iter().map(|m| m.to_string()).collect();
+        write!(
+            f,
+            "{:<7} {} // {} HTM, {} STM, {} ETM",
+            self.name,
+            moves.join(" "),
+            self.htm,
+            self.stm,
+            self.etm
+        )?;
+        if let Some(case) = &self.case {
+            write!(f, ", {}", case)?;
+        }
+        if let Some(reference) = self.reference {
+            write!(f, ", reference {}", reference)?;
+        }
+        if let Some(algorithm) = self.algorithm {
+            write!(f, ", algorithm {}", algorithm)?;
+        }
+        Ok(())
+    }
+}
+
+/// The reconstruction of a CFOP solve.
+///
+/// * `cross_face`: The face of the cross, None if no cross is solved.
+/// * `steps`: The steps in the order of the solve. The last step is unfinished if the solution does not solve the
+///   cube, a step which is solved together with the step before it has no moves.
+/// * `htm`, `stm`, `etm`: The number of moves of the solution.
+/// * `solved`: Whether the solution solves the cube.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reconstruction {
+    pub cross_face: Option<Color>,
+    pub steps: Vec<StepReport>,
+    pub htm: usize,
+    pub stm: usize,
+    pub etm: usize,
+    pub solved: bool,
+}
+
+impl Reconstruction {
+    /// The number of moves in the half turn metric more than the references of the steps.
+    pub fn excess(&self) -> usize {
+        self.steps.iter().map(|s| s.excess()).sum()
+    }
+}
+
+impl fmt::Display for Reconstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        write!(
+            f,
+            "Total: {} HTM, {} STM, {} ETM, {} HTM over the references",
+            self.htm,
+            self.stm,
+            self.etm,
+            self.excess()
+        )?;
+        if !self.solved {
+            write!(f, ", not solved")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    Cross,
+    F2L,
+    Oll,
+    Pll,
+    Done,
+}
+
+/// The F2L slots solved on the cube, in the D frame.
+fn solved_slots(cd: &CubieCube) -> Vec<usize> {
+    (0..D_SLOTS.len())
+        .filter(|slot| StepGoal::f2l_pair(*slot).is_solved(cd))
+        .collect()
+}
+
+fn is_last_layer_oriented(cd: &CubieCube) -> bool {
+    cd.co[..4].iter().all(|o| *o == 0) && cd.eo[..4].iter().all(|o| *o == 0)
+}
+
+/// The optimal number of moves which reach the goal from the cube start, in the D frame.
+fn step_reference(goal: StepGoal, start: &CubieCube, max_length: usize) -> Result<usize, Error> {
+    Ok(StepSolver::new(goal, &ALL_MOVES)?
+        .solve(start, max_length)?
+        .length)
+}
+
+/// The optimal number of moves which solve the cross and the slots of the cube end, from the cube start, in the D
+/// frame.
+fn f2l_reference(start: &CubieCube, end: &CubieCube, max_length: usize) -> Result<usize, Error> {
+    let slots = solved_slots(end);
+    let goal = StepGoal {
+        corners: slots.iter().map(|s| D_SLOTS[*s].0).collect(),
+        edges: [DR, DF, DL, DB]
+            .into_iter()
+            .chain(slots.iter().map(|s| D_SLOTS[*s].1))
+            .collect(),
+        ..Default::default()
+    };
+    step_reference(goal, start, max_length)
+}
+
+/// Reconstruct a CFOP solve: split the solution into the cross, the four F2L pairs, OLL and PLL and compare each step
+/// with a reference.
+///
+/// The scramble and the solution are given in the extended notation with wide moves, slice moves and rotations. The
+/// cross is the first cross on any face which is solved during the solution, the other steps are detected relative to
+/// it. A step ends with the move which solves it.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::reconstruction::reconstruct;
+///
+/// fn main() {
+///     let reconstruction = reconstruct("R U R' U' F2", "F2 U R U' R'").unwrap();
+///     assert!(reconstruction.solved);
+///     println!("{}", reconstruction);
+/// }
+/// ```
+pub fn reconstruct(scramble: &str, solution: &str) -> Result<Reconstruction, Error> {
+    let scramble = to_face_turns(&parse_moves(scramble)?);
+    let solution = parse_moves(solution)?;
+    let mut cc = CubieCube::from(&scramble);
+    let mut orientation = CubeOrientation::default();
+
+    let mut steps = Vec::new();
+    let mut stage = Stage::Cross;
+    let mut cross_face = None;
+    let mut s = CubieCube::default();
+    let mut pairs = 0;
+    let mut start = cc;
+    let mut moves = Vec::new();
+    for i in 0..=solution.len() {
+        if i > 0 {
+            let m = solution[i - 1];
+            cc = cc.apply_moves(&orientation.face_turns(&m));
+            moves.push(m);
+        }
+        // several steps may be solved by one move
+        loop {
+            let cd = conjugate(&s, &cc);
+            let htm = count_moves(&moves, Metric::Htm);
+            let (name, case, reference, algorithm) = match stage {
+                Stage::Cross => match ALL_COLORS.iter().find(|f| is_cross_solved(&cc, **f)) {
+                    Some(face) => {
+                        cross_face = Some(*face);
+                        s = rotation_to_d(*face);
+                        let length = solve_cross(&start, *face, false)?.length;
+                        ("Cross".to_string(), None, length, None)
+                    }
+                    None => break,
+                },
+                Stage::F2L => {
+                    if solved_slots(&cd).len() <= pairs {
+                        break;
+                    }
+                    pairs += 1;
+                    let length = f2l_reference(&conjugate(&s, &start), &cd, htm)?;
+                    (format!("F2L {}", pairs), None, length, None)
+                }
+                Stage::Oll => {
+                    if !is_last_layer_oriented(&cd) {
+                        break;
+                    }
+                    let cs = conjugate(&s, &start);
+                    let case = recognize_oll(&cs)?;
+                    (
+                        "OLL".to_string(),
+                        Some(case.name.clone()),
+                        step_reference(StepGoal::oll(), &cs, htm)?,
+                        Some(case.solution().len()),
+                    )
+                }
+                Stage::Pll => {
+                    if cc != CubieCube::default() {
+                        break;
+                    }
+                    let cs = conjugate(&s, &start);
+                    let case = recognize_pll(&cs)?;
+                    (
+                        "PLL".to_string(),
+                        Some(case.name.clone()),
+                        step_reference(StepGoal::pll(), &cs, htm)?,
+                        Some(case.solution().len()),
+                    )
+                }
+                Stage::Done => break,
+            };
+            steps.push(report(
+                name,
+                std::mem::take(&mut moves),
+                case,
+                Some(reference),
+                algorithm,
+            ));
+            start = cc;
+            stage = match stage {
+                Stage::Cross => Stage::F2L,
+                Stage::F2L if pairs < D_SLOTS.len() => Stage::F2L,
+                Stage::F2L => Stage::Oll,
+                Stage::Oll => Stage::Pll,
+                _ => Stage::Done,
+            };
+        }
+    }
+    if !moves.is_empty() {
+        match (stage, steps.last_mut()) {
+            // the moves after the solve, e.g. a rotation
+            (Stage::Done, Some(last)) => {
+                let mut last_moves = std::mem::take(&mut last.moves);
+                last_moves.extend(moves);
+                *last = report(
+                    last.name.clone(),
+                    last_moves,
+                    last.case.clone(),
+                    last.reference,
+                    last.algorithm,
+                );
+            }
+            _ => {
+                let name = match stage {
+                    Stage::Cross => "Cross".to_string(),
+                    Stage::F2L => format!("F2L {}", pairs + 1),
+                    Stage::Oll => "OLL".to_string(),
+                    _ => "PLL".to_string(),
+                };
+                steps.push(report(name, moves, None, None, None));
+            }
+        }
+    }
+
+    Ok(Reconstruction {
+        cross_face,
+        htm: count_moves(&solution, Metric::Htm),
+        stm: count_moves(&solution, Metric::Stm),
+        etm: count_moves(&solution, Metric::Etm),
+        solved: cc == CubieCube::default(),
+        steps,
+    })
+}
+
+fn report(
+    name: String,
+    moves: Vec<ExtendedMove>,
+    case: Option<String>,
+    reference: Option<usize>,
+    algorithm: Option<usize>,
+) -> StepReport {
+    StepReport {
+        name,
+        htm: count_moves(&moves, Metric::Htm),
+        stm: count_moves(&moves, Metric::Stm),
+        etm: count_moves(&moves, Metric::Etm),
+        moves,
+        case,
+        reference,
+        algorithm,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::reconstruction::*;
+
+    #[test]
+    fn test_reconstruct() {
+        let solution =
+            "R2 D' F2 y R U' R' y' F' U F L' U2 L U R U R' U R U2 R' M2 U M U2 M' U M2 U";
+        let moves = to_face_turns(&parse_moves(solution).unwrap());
+        let scramble: Vec<String> = crate::moves::invert_moves(&moves)
+            .into_iter()
+            .map(|m| ExtendedMove::from(m).to_string())
+            .collect();
+        let reconstruction = reconstruct(&scramble.join(" "), solution).unwrap();
+        assert_eq!(reconstruction.cross_face, Some(Color::D));
+        assert!(reconstruction.solved);
+        let names: Vec<&str> = reconstruction
+            .steps
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Cross", "F2L 1", "F2L 2", "F2L 3", "F2L 4", "OLL", "PLL"]
+        );
+        // the front left slot is not touched by the scramble
+        assert!(reconstruction.steps[1].moves.is_empty());
+        let f2l = &reconstruction.steps[2];
+        assert_eq!((f2l.htm, f2l.stm, f2l.etm), (3, 3, 4));
+        assert_eq!(f2l.reference, Some(3));
+        let pll = &reconstruction.steps[6];
+        assert_eq!(pll.case, Some("PLL Ua".to_string()));
+        assert_eq!((pll.htm, pll.stm, pll.etm), (12, 8, 8));
+        assert_eq!((pll.reference, pll.algorithm), (Some(10), Some(12)));
+        assert_eq!(
+            (reconstruction.htm, reconstruction.stm, reconstruction.etm),
+            (32, 28, 30)
+        );
+        assert_eq!(reconstruction.excess(), 4);
+
+        // an unfinished solve ends with the unfinished step
+        let reconstruction = reconstruct(&scramble.join(" "), "R2 D' F2 y R U'").unwrap();
+        assert!(!reconstruction.solved);
+        let last = reconstruction.steps.last().unwrap();
+        assert_eq!(last.name, "F2L 2");
+        assert_eq!(last.reference, None);
+
+        assert!(reconstruct("R U", "R X").is_err());
+    }
+
+    #[test]
+    fn test_references() {
+        let scramble = "U' R2 B L U2 L D' F' U L2 R' U' L2 D' L2 B U2 D2 R2 F";
+        let solution = "U R2 U2 L' B D' U R U R' U' L2 B2 L2 B2 U F U L U2 L' F' U L F U2 F' L' \
+            U F U R U' R' F' R2 L2 D R2 L2 U2 R2 L2 D R2 L2 U'";
+        let reconstruction = reconstruct(scramble, solution).unwrap();
+        assert!(reconstruction.solved);
+        // the algorithms of the database are not optimal
+        let oll = &reconstruction.steps[5];
+        assert_eq!(oll.case, Some("OLL 44".to_string()));
+        assert_eq!(
+            (oll.htm, oll.reference, oll.algorithm),
+            (7, Some(6), Some(7))
+        );
+        let pll = &reconstruction.steps[6];
+        assert_eq!(pll.case, Some("PLL H".to_string()));
+        assert_eq!(
+            (pll.htm, pll.reference, pll.algorithm),
+            (12, Some(9), Some(12))
+        );
+        assert_eq!(oll.excess() + pll.excess(), 4);
+        assert!(reconstruction.excess() >= 4);
+    }
+}
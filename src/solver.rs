@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::cmp::{max, min};
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -8,10 +9,12 @@ use std::time::{Duration, Instant};
 use crate::constants::*;
 use crate::coord::{self, CoordCube, EdgeMergeTables};
 use crate::cubie::CubieCube;
+use crate::enumerate::{self, EnumerateOptions};
 use crate::error::Error;
 use crate::facelet::FaceCube;
 use crate::moves::Move;
 use crate::moves::{self, MoveTables};
+use crate::patterns::Pattern;
 use crate::pruning::PrunningTables;
 use crate::symmetries::SymmetriesTables;
 use crate::{pruning, symmetries};
@@ -23,10 +26,10 @@ use crate::{pruning, symmetries};
 /// * `pr`: [PrunningTables]
 /// * `em`: [EdgeMergeTables]
 pub struct SolverTables {
-    sy: SymmetriesTables,
-    mv: MoveTables,
-    pr: PrunningTables,
-    em: EdgeMergeTables,
+    pub sy: SymmetriesTables,
+    pub mv: MoveTables,
+    pub pr: PrunningTables,
+    pub em: EdgeMergeTables,
 }
 
 impl SolverTables {
@@ -45,15 +48,179 @@ impl SolverTables {
             em: em,
         }
     }
+
+    /// Exact number of moves which bring the cube co into the subgroup H = <U, D, R2, F2, L2, B2>.
+    ///
+    /// This is also a lower bound for the number of moves to solve the cube.
+    pub fn phase1_distance(&self, co: &CoordCube) -> u16 {
+        self.phase1_depth(co.flip, co.twist, co.slice_sorted) as u16
+    }
+
+    /// Lower bound for the number of moves of H which solve the cube co, from the phase 2 pruning table over
+    /// corners and ud_edges. The table covers up to 10 moves, 11 means at least 11 moves.
+    ///
+    /// None if the cube is not in H.
+    pub fn phase2_bound(&self, co: &CoordCube) -> Option<u16> {
+        in_h(co).then(|| self.phase2_depth(co.corners, co.ud_edges))
+    }
+
+    /// Exact number of moves of H which solve the corners and the UD-slice edges of the cube co, a lower bound
+    /// for the number of moves of H which solve the cube.
+    ///
+    /// None if the cube is not in H.
+    pub fn cornslice_bound(&self, co: &CoordCube) -> Option<u16> {
+        in_h(co).then(|| {
+            self.pr.cornslice_depth[N_PERM_4 * co.corners as usize + co.slice_sorted as usize]
+        })
+    }
+
+    /// Compute the distance to the cube subgroup H where flip=slice=twist=0
+    ///
+    /// return: The distance to H
+    pub(crate) fn phase1_depth(&self, flip: u16, twist: u16, slice_sorted: u16) -> u32 {
+        let mut slice_ = slice_sorted / N_PERM_4 as u16;
+        let mut flip = flip;
+        let mut twist = twist;
+        let flipslice = (N_FLIP * slice_ as usize) + flip as usize;
+        let classidx = self.sy.flipslice_classidx[flipslice];
+        let sym = self.sy.flipslice_sym[flipslice];
+        let mut depth_mod3 = self.pr.get_flipslice_twist_depth3(
+            N_TWIST * classidx as usize
+                + self.sy.twist_conj[((twist as usize) << 4) + sym as usize] as usize,
+        );
+
+        let mut depth = 0;
+        while flip != SOLVED || slice_ != SOLVED || twist != SOLVED {
+            if depth_mod3 == 0 {
+                depth_mod3 = 3;
+            }
+            for m in ALL_MOVES {
+                let twist1 = self.mv.twist_move[N_MOVE * twist as usize + m as usize];
+                let flip1 = self.mv.flip_move[N_MOVE * flip as usize + m as usize];
+                let slice1 = self.mv.slice_sorted_move
+                    [N_MOVE * slice_ as usize * N_PERM_4 + m as usize]
+                    / N_PERM_4 as u16;
+                let flipslice1 = N_FLIP * slice1 as usize + flip1 as usize;
+                let classidx1 = self.sy.flipslice_classidx[flipslice1];
+                let sym = self.sy.flipslice_sym[flipslice1];
+                if self.pr.get_flipslice_twist_depth3(
+                    N_TWIST * classidx1 as usize
+                        + self.sy.twist_conj[((twist1 as usize) << 4) + sym as usize]
+                            as usize,
+                ) == depth_mod3 - 1
+                {
+                    depth += 1;
+                    twist = twist1;
+                    flip = flip1;
+                    slice_ = slice1;
+                    depth_mod3 -= 1;
+                    break;
+                }
+            }
+        }
+        depth
+    }
+
+    /**
+    Get distance to subgroup where only the UD-slice edges may be permuted in their slice (only 24/2 = 12 possible
+    ways due to overall even parity). This is a lower bound for the number of moves to solve phase 2.
+
+    # Parameters
+
+    `corners`: Corners coordinate
+
+    `ud_edges`: Coordinate of the 8 edges of U and D face.
+
+    return:
+    */
+    pub(crate) fn phase2_depth(&self, corners: u16, ud_edges: u16) -> u16 {
+        let mut corners = corners;
+        let mut ud_edges = ud_edges;
+        let classidx = self.sy.corner_classidx[corners as usize];
+        let sym = self.sy.corner_sym[corners as usize];
+
+        let mut depth_mod3 = self.pr.get_corners_ud_edges_depth3(
+            N_UD_EDGES * classidx as usize
+                + self.sy.ud_edges_conj[((ud_edges as usize) << 4) + sym as usize]
+                    as usize,
+        );
+        if depth_mod3 == 3 {
+            // unfilled entry, depth >= 11
+            return 11;
+        }
+        let mut depth = 0;
+        while corners != SOLVED || ud_edges != SOLVED {
+            if depth_mod3 == 0 {
+                depth_mod3 = 3;
+            }
+            // only iterate phase 2 moves
+            for m in [
+                Move::U,
+                Move::U2,
+                Move::U3,
+                Move::R2,
+                Move::F2,
+                Move::D,
+                Move::D2,
+                Move::D3,
+                Move::L2,
+                Move::B2,
+            ] {
+                let corners1 =
+                    self.mv.corners_move[N_MOVE * corners as usize + m as usize];
+                let ud_edges1 =
+                    self.mv.ud_edges_move[N_MOVE * ud_edges as usize + m as usize];
+                let classidx1 = self.sy.corner_classidx[corners1 as usize];
+                let sym = self.sy.corner_sym[corners1 as usize];
+                if self.pr.get_corners_ud_edges_depth3(
+                    N_UD_EDGES * classidx1 as usize
+                        + self.sy.ud_edges_conj
+                            [((ud_edges1 as usize) << 4) + sym as usize]
+                            as usize,
+                ) == depth_mod3 - 1
+                {
+                    depth += 1;
+                    corners = corners1;
+                    ud_edges = ud_edges1;
+                    depth_mod3 -= 1;
+                    break;
+                }
+            }
+        }
+        depth
+    }
 }
 
+/// Check if the cube co is in the subgroup H, where the phase 2 coordinates are valid.
+fn in_h(co: &CoordCube) -> bool {
+    co.twist == 0 && co.flip == 0 && co.slice_sorted < N_PERM_4 as u16
+}
+
+lazy_static! {
+    /// The data tables shared by all solver threads, created/loaded on first use.
+    pub(crate) static ref SOLVERTABLES: SolverTables = SolverTables::new();
+}
+
+/// Give the data tables used by the solver, they are created/loaded on the first call.
+///
+/// Use them to work on the coordinate level, e.g. with [CoordCube::phase1_move], without loading the tables again.
+pub fn solver_tables() -> &'static SolverTables {
+    &SOLVERTABLES
+}
+
+/// Definition string of the solved cube, the default goal.
+pub(crate) const GOALSTRING: &str = "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB";
+
 /// Solution result:
 /// * solution: a Move vector.
 /// * solve_time: time to get solution(not include load data tables time.).
+/// * nodes: number of search nodes visited.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SoutionResult {
     pub solution: Vec<Move>,
     pub solve_time: Duration,
+    #[serde(default)]
+    pub nodes: u64,
 }
 
 impl Default for SoutionResult {
@@ -61,6 +228,89 @@ impl Default for SoutionResult {
         Self {
             solution: Vec::new(),
             solve_time: Duration::from_secs(0),
+            nodes: 0,
+        }
+    }
+}
+
+/// How the budgets of [SearchLimits] (timeout and node count) are combined.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimitPolicy {
+    /// Stop when one of the given budgets is exhausted.
+    #[default]
+    Any,
+    /// Stop when all of the given budgets are exhausted.
+    All,
+}
+
+impl LimitPolicy {
+    /// Combine the budgets, None for a budget which is not given.
+    fn exhausted(self, budgets: &[Option<bool>]) -> bool {
+        let mut given = budgets.iter().flatten();
+        match self {
+            LimitPolicy::Any => given.any(|b| *b),
+            LimitPolicy::All => budgets.iter().any(|b| b.is_some()) && given.all(|b| *b),
+        }
+    }
+}
+
+/// Limits of a search.
+///
+/// * `max_length`: The search stops if a maneuver of length <= max_length has been found.
+/// * `timeout`: Search time budget in seconds. Not checked by the single-threaded solver on wasm32, there is no clock.
+/// * `max_nodes`: Budget of search nodes visited by all searches of the cube. Unlike the timeout this gives the
+///   same result on every machine, as long as the search is single-threaded.
+/// * `max_phase1_length`: Maximal number of phase 1 moves of a solution.
+/// * `max_phase2_length`: Maximal number of phase 2 moves of a solution.
+/// * `policy`: How the timeout and node budgets are combined, see [LimitPolicy].
+/// * `hard`: By default the budgets are only checked after a first solution has been found. If hard is set, they
+///   also stop a search without solution, which then gives [Error::NoSolution].
+/// * `self_check`: Apply the solution to the cube with [verify_solution] before it is returned, a solution which does
+///   not reach the goal gives [Error::InvalidSolution].
+///
+/// The default limits search a solution with at most 20 moves without any budget.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::solver::{solver_with_limits, SearchLimits};
+///
+/// fn main() {
+///     let limits = SearchLimits {
+///         max_length: 18,
+///         max_nodes: Some(200_000),
+///         ..Default::default()
+///     };
+///     let result = solver_with_limits(
+///         "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF",
+///         "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB",
+///         &limits,
+///         ).unwrap();
+///     println!("{:?}, ({}), ({})", result.solution, result.solution.len(), result.nodes);
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct SearchLimits {
+    pub max_length: usize,
+    pub timeout: Option<f32>,
+    pub max_nodes: Option<u64>,
+    pub max_phase1_length: u16,
+    pub max_phase2_length: u16,
+    pub policy: LimitPolicy,
+    pub hard: bool,
+    pub self_check: bool,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            max_length: 20,
+            timeout: None,
+            max_nodes: None,
+            max_phase1_length: 19,
+            max_phase2_length: 10,
+            policy: LimitPolicy::Any,
+            hard: false,
+            self_check: false,
         }
     }
 }
@@ -95,77 +345,134 @@ pub fn solver(
     max_length: usize,
     time_out: f32,
 ) -> Result<SoutionResult, Error> {
-    lazy_static! {
-        static ref SOLVERTABLES: SolverTables = SolverTables::new();
+    let limits = SearchLimits {
+        max_length,
+        timeout: Some(time_out),
+        ..Default::default()
+    };
+    solver_with_limits(cubestring, goalstring, &limits)
+}
+
+/// Solve a cube defined by cubstring to a position defined by goalstring within the given [SearchLimits].
+///
+/// Gives [Error::NoSolution] if the limits do not allow any solution.
+pub fn solver_with_limits(
+    cubestring: &str,
+    goalstring: &str,
+    search_limits: &SearchLimits,
+) -> Result<SoutionResult, Error> {
+    let (start, goal) = parse_cubes(cubestring, goalstring)?;
+    search(&start, &goal, search_limits)
+}
+
+/// Solve the cube start to the cube goal, the solution applied to start gives goal.
+///
+/// Unlike [solver_with_limits] the error tells whether start ([Error::InvalidStart]) or goal
+/// ([Error::InvalidGoal]) is not a valid cube.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::patterns::Pattern;
+/// use kociemba::solver::{solve_cubie, SearchLimits};
+///
+/// fn main() {
+///     let start = Pattern::Checkerboard.cube();
+///     let goal = Pattern::CubeInCube.cube();
+///     let result = solve_cubie(&start, &goal, &SearchLimits::default()).unwrap();
+///     assert_eq!(start.apply_moves(&result.solution), goal);
+/// }
+/// ```
+pub fn solve_cubie(
+    start: &CubieCube,
+    goal: &CubieCube,
+    limits: &SearchLimits,
+) -> Result<SoutionResult, Error> {
+    search(start, goal, limits)
+}
+
+/// Check that the moves transform the cube start into the cube goal.
+///
+/// Gives [Error::InvalidStart] or [Error::InvalidGoal] for an invalid cube and [Error::InvalidSolution] if the moves
+/// applied to start do not give goal.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::cubie::CubieCube;
+/// use kociemba::moves::Move::*;
+/// use kociemba::solver::verify_solution;
+///
+/// fn main() {
+///     let start = CubieCube::from(&vec![R, U]);
+///     assert!(verify_solution(&start, &CubieCube::default(), &[U3, R3]).is_ok());
+///     assert!(verify_solution(&start, &CubieCube::default(), &[R3, U3]).is_err());
+/// }
+/// ```
+pub fn verify_solution(start: &CubieCube, goal: &CubieCube, moves: &[Move]) -> Result<(), Error> {
+    start.verify().map_err(|e| Error::InvalidStart(Box::new(e)))?;
+    goal.verify().map_err(|e| Error::InvalidGoal(Box::new(e)))?;
+    match start.apply_moves(moves) == *goal {
+        true => Ok(()),
+        false => Err(Error::InvalidSolution),
     }
+}
+
+/// Solve the cube start to the cube goal given on the facelet level, see [solve_cubie].
+pub fn solve_facecube(
+    start: &FaceCube,
+    goal: &FaceCube,
+    limits: &SearchLimits,
+) -> Result<SoutionResult, Error> {
+    let start = CubieCube::try_from(start).map_err(|e| Error::InvalidStart(Box::new(e)))?;
+    let goal = CubieCube::try_from(goal).map_err(|e| Error::InvalidGoal(Box::new(e)))?;
+    solve_cubie(&start, &goal, limits)
+}
+
+/// Solve the cube start to the [Pattern] pattern, see [solve_cubie].
+pub fn solve_to_pattern(
+    start: &CubieCube,
+    pattern: Pattern,
+    limits: &SearchLimits,
+) -> Result<SoutionResult, Error> {
+    solve_cubie(start, &pattern.cube(), limits)
+}
+
+/// Search the maneuver which transforms the cube start into the cube goal with six threads.
+fn search(
+    start: &CubieCube,
+    goal: &CubieCube,
+    search_limits: &SearchLimits,
+) -> Result<SoutionResult, Error> {
+    let cc = prepare_cubie(start, goal)?;
     for i in 0..2 {
-        let facestr;
-        let maxlength;
-        let timeout;
-        let mut cc;
-        if i == 0 {
-            facestr = "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF";
-            maxlength = 25;
-            timeout = 3.;
-            let fc = FaceCube::try_from(facestr)?;
-            cc = CubieCube::try_from(&fc)?;
+        let limits;
+        let cc = if i == 0 {
+            limits = SearchLimits {
+                max_length: 25,
+                timeout: Some(3.),
+                ..Default::default()
+            };
+            let fc = FaceCube::try_from("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF")?;
+            CubieCube::try_from(&fc)?
         } else {
-            facestr = &cubestring;
-            maxlength = max_length;
-            timeout = time_out;
-            let fc0 = FaceCube::try_from(facestr)?;
-            let fcg = FaceCube::try_from(goalstring)?;
-            let cc0 = CubieCube::try_from(&fc0)?;
-            let s = cc0.verify()?;
-            if s != true {
-                return Err(Error::InvalidFaceletString); // no valid facelet cube, gives invalid cubie cube
-            }
-            let ccg = CubieCube::try_from(&fcg)?;
-            let s = ccg.verify()?;
-            if s != true {
-                return Err(Error::InvalidFaceletString); // no valid facelet cube, gives invalid cubie cube
-            }
-            // cc0 * S = ccg  <=> (ccg^-1 * cc0) * S = Id
-            cc = ccg.inverse_cubie_cube();
-            cc.multiply(cc0);
-        }
+            limits = *search_limits;
+            cc
+        };
 
         let start_time = Instant::now();
-        let syms = cc.symmetries();
-        let v: HashSet<usize> = HashSet::from([16, 20, 24, 28]);
-        let symsset: HashSet<usize> = HashSet::from_iter(syms.into_iter());
-        let ins: Vec<&usize> = v.intersection(&symsset).collect();
-        let mut tr = match ins.len() > 0 {
-            // we have some rotational symmetry along a long diagonal
-            true => vec![0, 3], // so we search only one direction and the inverse
-            false => (0..6).collect(), // This means search in 3 directions + inverse cube
-        };
-        let vv: HashSet<usize> = HashSet::from_iter(48..96);
-        let ins: Vec<&usize> = vv.intersection(&symsset).collect();
-        if ins.len() > 0 {
-            // we have some antisymmetry so we do not search the inverses
-            tr = tr.into_iter().filter(|x| *x < 3).collect()
-        }
+        let tr = search_directions(&cc);
         let mut solverthreads = vec![];
 
-        // these mutable variables are modidified by all six threads
-        let solutions: Arc<Mutex<Vec<Vec<Move>>>> = Arc::new(Mutex::new(Vec::new()));
-        let terminated = Arc::new(Mutex::new(false));
+        // this state is modidified by all six threads
+        let shared = Arc::new(SharedSearch::default());
 
         for i in tr {
-            let solutions = Arc::clone(&solutions);
-            let terminated = Arc::clone(&terminated);
-
             let mut sth = SolverThread::new(
                 cc,
                 i % 3,
                 i / 3,
-                maxlength,
-                timeout,
-                start_time,
-                solutions,
-                terminated,
-                vec![999],
+                limits,
+                Some(start_time),
+                Arc::clone(&shared),
                 &SOLVERTABLES,
             );
 
@@ -179,19 +486,78 @@ pub fn solver(
         }
 
         if i == 1 {
-            let solutions = solutions.lock().unwrap();
-            if (*solutions).len() > 0 {
-                let end_time = Instant::now();
-                let ls = (*solutions).last().unwrap();
-                // println!("{:?} ({}), {:?}", *ls, (*ls).len(), end_time - start_time)
-                return Ok(SoutionResult {
-                    solution: ls.to_vec(),
-                    solve_time: end_time - start_time,
-                });
-            }
+            return checked_result(&shared, start, goal, &limits, start_time.elapsed());
         }
     }
-    Ok(SoutionResult::default())
+    Err(Error::NoSolution)
+}
+
+/// Give the result of the searches for the maneuver from start to goal, checked with [verify_solution] against these
+/// cubes if `limits.self_check` is set.
+pub(crate) fn checked_result(
+    shared: &SharedSearch,
+    start: &CubieCube,
+    goal: &CubieCube,
+    limits: &SearchLimits,
+    solve_time: Duration,
+) -> Result<SoutionResult, Error> {
+    let result = shared.result(solve_time)?;
+    if limits.self_check {
+        verify_solution(start, goal, &result.solution)?;
+    }
+    Ok(result)
+}
+
+/// Check the cube definition strings and give the cubes defined by cubestring and goalstring.
+pub(crate) fn parse_cubes(
+    cubestring: &str,
+    goalstring: &str,
+) -> Result<(CubieCube, CubieCube), Error> {
+    let fc0 = FaceCube::try_from(cubestring)?;
+    let fcg = FaceCube::try_from(goalstring)?;
+    let cc0 = CubieCube::try_from(&fc0)?;
+    let s = cc0.verify()?;
+    if s != true {
+        return Err(Error::InvalidFaceletString); // no valid facelet cube, gives invalid cubie cube
+    }
+    let ccg = CubieCube::try_from(&fcg)?;
+    let s = ccg.verify()?;
+    if s != true {
+        return Err(Error::InvalidFaceletString); // no valid facelet cube, gives invalid cubie cube
+    }
+    Ok((cc0, ccg))
+}
+
+/// Check the cubes start and goal and give the cube which has to be solved to transform start into goal.
+pub(crate) fn prepare_cubie(start: &CubieCube, goal: &CubieCube) -> Result<CubieCube, Error> {
+    start.verify().map_err(|e| Error::InvalidStart(Box::new(e)))?;
+    goal.verify().map_err(|e| Error::InvalidGoal(Box::new(e)))?;
+    // start * S = goal  <=> (goal^-1 * start) * S = Id
+    let mut cc = goal.inverse_cubie_cube();
+    cc.multiply(*start);
+    Ok(cc)
+}
+
+/// Give the directions in which the cube cc is searched, one [SolverThread] per direction.
+///
+/// Direction i means rot = i % 3 and inv = i / 3.
+pub(crate) fn search_directions(cc: &CubieCube) -> Vec<u8> {
+    let syms = cc.symmetries();
+    let v: HashSet<usize> = HashSet::from([16, 20, 24, 28]);
+    let symsset: HashSet<usize> = HashSet::from_iter(syms.into_iter());
+    let ins: Vec<&usize> = v.intersection(&symsset).collect();
+    let mut tr = match ins.len() > 0 {
+        // we have some rotational symmetry along a long diagonal
+        true => vec![0, 3], // so we search only one direction and the inverse
+        false => (0..6).collect(), // This means search in 3 directions + inverse cube
+    };
+    let vv: HashSet<usize> = HashSet::from_iter(48..96);
+    let ins: Vec<&usize> = vv.intersection(&symsset).collect();
+    if ins.len() > 0 {
+        // we have some antisymmetry so we do not search the inverses
+        tr = tr.into_iter().filter(|x| *x < 3).collect()
+    }
+    tr
 }
 
 /// Solve a cube defined by its cube definition string.
@@ -214,8 +580,168 @@ pub fn solver(
 /// ```
 
 pub fn solve(cubestring: &str, max_length: usize, timeout: f32) -> Result<SoutionResult, Error> {
-    let goalstring = "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB";
-    solver(cubestring, goalstring, max_length, timeout)
+    solver(cubestring, GOALSTRING, max_length, timeout)
+}
+
+/// Solve a cube defined by cubestring to a position defined by goalstring without spawning threads.
+///
+/// The rotated/inverted cubes are searched one after another for each search depth, so the result only
+/// depends on the input and on `max_nodes`, not on thread timing or machine speed. This also works on
+/// targets without threads and clock like `wasm32-unknown-unknown`. There is no file system there either, so
+/// the first solve creates all data tables in memory, which takes about 15 seconds in a native release build and
+/// several times longer in a browser.
+///
+/// # Parameters
+/// * `cubestring`: The format of the string is given in the Facelet class defined.
+/// * `goalstring`: The format of the string is given in the Facelet class defined.
+/// * `max_length`: The function will return if a maneuver of length <= max_length has been found
+/// * `max_nodes`: If more search nodes have been visited, the best solution found so far is returned. If there has
+///   not been found any solution yet the computation continues until a first solution appears.
+///
+/// `solve_time` of the result is zero on wasm32, there is no clock there.
+///
+/// Use [solver_single_threaded_with_limits] for other limits.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::solver::solver_single_threaded;
+///
+/// fn main() {
+///     let result = solver_single_threaded(
+///         "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF",
+///         "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB",
+///         20,
+///         1_000_000,
+///         ).unwrap();
+///     println!("{:?}, ({})", result.solution, result.solution.len());
+/// }
+/// ```
+pub fn solver_single_threaded(
+    cubestring: &str,
+    goalstring: &str,
+    max_length: usize,
+    max_nodes: u64,
+) -> Result<SoutionResult, Error> {
+    let limits = SearchLimits {
+        max_length,
+        max_nodes: Some(max_nodes),
+        ..Default::default()
+    };
+    solver_single_threaded_with_limits(cubestring, goalstring, &limits)
+}
+
+/// Solve a cube defined by cubestring to a position defined by goalstring within the given [SearchLimits]
+/// without spawning threads, see [solver_single_threaded].
+pub fn solver_single_threaded_with_limits(
+    cubestring: &str,
+    goalstring: &str,
+    limits: &SearchLimits,
+) -> Result<SoutionResult, Error> {
+    let (start, goal) = parse_cubes(cubestring, goalstring)?;
+    let cc = prepare_cubie(&start, &goal)?;
+    let start_time = now();
+    let shared = Arc::new(SharedSearch::default());
+    let mut solverthreads = direction_threads(cc, limits, start_time, &shared);
+    for togo1 in 0..=limits.max_phase1_length {
+        for (sth, dist) in solverthreads.iter_mut() {
+            if togo1 >= *dist {
+                sth.search_depth(*dist, togo1);
+            }
+        }
+        if shared.terminated.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+    let solve_time = start_time.map_or(Duration::ZERO, |t| t.elapsed());
+    checked_result(&shared, &start, &goal, limits, solve_time)
+}
+
+/// The current time, None on wasm32 where there is no clock.
+pub(crate) fn now() -> Option<Instant> {
+    match cfg!(target_arch = "wasm32") {
+        true => None,
+        false => Some(Instant::now()),
+    }
+}
+
+/// Create and initialize the [SolverThread]s for the cube cc without starting them, with the distance
+/// to H of their rotated/inverted cube. They are run cooperatively on the calling thread.
+pub(crate) fn direction_threads(
+    cc: CubieCube,
+    limits: &SearchLimits,
+    start_time: Option<Instant>,
+    shared: &Arc<SharedSearch>,
+) -> Vec<(SolverThread<'static>, u16)> {
+    search_directions(&cc)
+        .into_iter()
+        .map(|i| {
+            let mut sth = SolverThread::new(
+                cc,
+                i % 3,
+                i / 3,
+                *limits,
+                start_time,
+                Arc::clone(shared),
+                &SOLVERTABLES,
+            );
+            let dist = sth.init();
+            (sth, dist)
+        })
+        .collect()
+}
+
+/// Solve a cube defined by its cube definition string without spawning threads, see [solver_single_threaded].
+pub fn solve_single_threaded(
+    cubestring: &str,
+    max_length: usize,
+    max_nodes: u64,
+) -> Result<SoutionResult, Error> {
+    solver_single_threaded(cubestring, GOALSTRING, max_length, max_nodes)
+}
+
+/// State shared by the [SolverThread]s which search the same cube.
+///
+/// * `solutions`: The solutions found so far, each one shorter than the one before
+/// * `terminated`: Signals a termination request to all searches
+/// * `shortest_length`: The length of the shortest solution found by any of the searches, all of them prune against it
+/// * `nodes`: The number of search nodes visited by all searches, updated each time phase 1 is solved
+/// * `enumerate`: If given, all accepted solutions are collected instead of searching ever shorter ones
+/// * `enumerated`: The normalized solutions seen so far while enumerating, accepted or not
+pub struct SharedSearch {
+    pub solutions: Mutex<Vec<Vec<Move>>>,
+    pub terminated: AtomicBool,
+    pub shortest_length: AtomicUsize,
+    pub nodes: AtomicU64,
+    pub enumerate: Option<EnumerateOptions>,
+    pub enumerated: Mutex<HashSet<Vec<Move>>>,
+}
+
+impl SharedSearch {
+    /// Give the shortest solution found, [Error::NoSolution] if there is none.
+    pub fn result(&self, solve_time: Duration) -> Result<SoutionResult, Error> {
+        let solutions = self.solutions.lock().unwrap();
+        match solutions.last() {
+            Some(solution) => Ok(SoutionResult {
+                solution: solution.clone(),
+                solve_time,
+                nodes: self.nodes.load(Ordering::Relaxed),
+            }),
+            None => Err(Error::NoSolution),
+        }
+    }
+}
+
+impl Default for SharedSearch {
+    fn default() -> Self {
+        Self {
+            solutions: Mutex::new(Vec::new()),
+            terminated: AtomicBool::new(false),
+            shortest_length: AtomicUsize::new(999),
+            nodes: AtomicU64::new(0),
+            enumerate: None,
+            enumerated: Mutex::new(HashSet::new()),
+        }
+    }
 }
 
 /** The SolverThread class solves implements the two phase algorithm.
@@ -223,16 +749,12 @@ pub fn solve(cubestring: &str, max_length: usize, timeout: f32) -> Result<Soutio
 * `cb_cube`: The cube to be solved in CubieCube representation
 * `rot`: Rotates the  cube 120° * rot along the long diagonal before applying the two-phase-algorithm
 * `inv`: 0: Do not invert the cube . 1: Invert the cube before applying the two-phase-algorithm
-* `ret_length`: If a solution with length <= ret_length is found the search stops.
+* `limits`: The [SearchLimits]. If a solution with length <= max_length is found the search stops.
  The most efficient way to solve a cube is to start six threads in parallel with rot = 0, 1 and 2 and
  inv = 0, 1. The first thread which finds a solutions sets the terminated flag which signals all other threads
  to teminate. On average this solves a cube about 12 times faster than solving one cube with a single thread.
-* `timeout`: Essentially the maximal search time in seconds. Essentially because the search does not return
- before at least one solution has been found.
-* `start_time`: The time the search started.
-* `solutions`: An array with the found solutions found by the six parallel threads
-* `terminated`: An event shared by the six threads to signal a termination request
-* `shortest_length`: The length of the shortest solutions in the solution array
+* `start_time`: The time the search started, the timeout is not checked if it is None.
+* `shared`: The [SharedSearch] state of the six threads: solutions, termination request, shortest length and nodes.
 * `solvertables`: The reference to [SolverTables].
 */
 pub struct SolverThread<'a> {
@@ -243,14 +765,13 @@ pub struct SolverThread<'a> {
     sofar_phase1: Vec<Move>,
     sofar_phase2: Vec<Move>,
     phase2_done: bool,
-    ret_length: usize,
-    timeout: f32,
-    start_time: Instant,
+    limits: SearchLimits,
+    start_time: Option<Instant>,
+    total_length: Option<u16>, // set while searching solutions of one length
+    nodes: u64, // nodes not yet added to shared.nodes
     cornersave: u16,
-    // these variables are shared by the six threads, initialized in function solve
-    solutions: Arc<Mutex<Vec<Vec<Move>>>>,
-    terminated: Arc<Mutex<bool>>,
-    shortest_length: Vec<usize>,
+    // shared by the six threads, initialized in function solve
+    shared: Arc<SharedSearch>,
     solvertables: &'a SolverTables,
 }
 
@@ -259,12 +780,9 @@ impl<'a> SolverThread<'a> {
         cb_cube: CubieCube,
         rot: u8,
         inv: u8,
-        ret_length: usize,
-        timeout: f32,
-        start_time: Instant,
-        solutions: Arc<Mutex<Vec<Vec<Move>>>>,
-        terminated: Arc<Mutex<bool>>,
-        shortest_length: Vec<usize>,
+        limits: SearchLimits,
+        start_time: Option<Instant>,
+        shared: Arc<SharedSearch>,
         solvertables: &'a SolverTables,
     ) -> Self {
         let co_cube = CoordCube::default();
@@ -276,13 +794,12 @@ impl<'a> SolverThread<'a> {
             sofar_phase1: Vec::new(),
             sofar_phase2: Vec::new(),
             phase2_done: false,
-            ret_length,
-            timeout,
+            limits,
             start_time,
+            total_length: None,
+            nodes: 0,
             cornersave: 0,
-            solutions,
-            terminated,
-            shortest_length,
+            shared,
             solvertables,
         }
     }
@@ -291,116 +808,13 @@ impl<'a> SolverThread<'a> {
     ///
     /// return: The distance to H
     fn get_depth_phase1(&self) -> u32 {
-        let mut slice_ = self.co_cube.slice_sorted / N_PERM_4 as u16;
-        let mut flip = self.co_cube.flip;
-        let mut twist = self.co_cube.twist;
-        let flipslice = (N_FLIP * slice_ as usize) + flip as usize;
-        let classidx = self.solvertables.sy.flipslice_classidx[flipslice];
-        let sym = self.solvertables.sy.flipslice_sym[flipslice];
-        let mut depth_mod3 = self.solvertables.pr.get_flipslice_twist_depth3(
-            N_TWIST * classidx as usize
-                + self.solvertables.sy.twist_conj[((twist as usize) << 4) + sym as usize] as usize,
-        );
-
-        let mut depth = 0;
-        while flip != SOLVED || slice_ != SOLVED || twist != SOLVED {
-            if depth_mod3 == 0 {
-                depth_mod3 = 3;
-            }
-            for m in ALL_MOVES {
-                let twist1 = self.solvertables.mv.twist_move[N_MOVE * twist as usize + m as usize];
-                let flip1 = self.solvertables.mv.flip_move[N_MOVE * flip as usize + m as usize];
-                let slice1 = self.solvertables.mv.slice_sorted_move
-                    [N_MOVE * slice_ as usize * N_PERM_4 + m as usize]
-                    / N_PERM_4 as u16;
-                let flipslice1 = N_FLIP * slice1 as usize + flip1 as usize;
-                let classidx1 = self.solvertables.sy.flipslice_classidx[flipslice1];
-                let sym = self.solvertables.sy.flipslice_sym[flipslice1];
-                if self.solvertables.pr.get_flipslice_twist_depth3(
-                    N_TWIST * classidx1 as usize
-                        + self.solvertables.sy.twist_conj[((twist1 as usize) << 4) + sym as usize]
-                            as usize,
-                ) == depth_mod3 - 1
-                {
-                    depth += 1;
-                    twist = twist1;
-                    flip = flip1;
-                    slice_ = slice1;
-                    depth_mod3 -= 1;
-                    break;
-                }
-            }
-        }
-        depth
+        self.solvertables
+            .phase1_depth(self.co_cube.flip, self.co_cube.twist, self.co_cube.slice_sorted)
     }
 
-    /**
-    Get distance to subgroup where only the UD-slice edges may be permuted in their slice (only 24/2 = 12 possible
-    ways due to overall even parity). This is a lower bound for the number of moves to solve phase 2.
-
-    # Parameters
-
-    `corners`: Corners coordinate
-
-    `ud_edges`: Coordinate of the 8 edges of U and D face.
-
-    return:
-    */
+    /// Get a lower bound for the number of moves to solve phase 2, see [SolverTables::phase2_depth].
     fn get_depth_phase2(&self, corners: u16, ud_edges: u16) -> u16 {
-        let mut corners = corners;
-        let mut ud_edges = ud_edges;
-        let classidx = self.solvertables.sy.corner_classidx[corners as usize];
-        let sym = self.solvertables.sy.corner_sym[corners as usize];
-
-        let mut depth_mod3 = self.solvertables.pr.get_corners_ud_edges_depth3(
-            N_UD_EDGES * classidx as usize
-                + self.solvertables.sy.ud_edges_conj[((ud_edges as usize) << 4) + sym as usize]
-                    as usize,
-        );
-        if depth_mod3 == 3 {
-            // unfilled entry, depth >= 11
-            return 11;
-        }
-        let mut depth = 0;
-        while corners != SOLVED || ud_edges != SOLVED {
-            if depth_mod3 == 0 {
-                depth_mod3 = 3;
-            }
-            // only iterate phase 2 moves
-            for m in [
-                Move::U,
-                Move::U2,
-                Move::U3,
-                Move::R2,
-                Move::F2,
-                Move::D,
-                Move::D2,
-                Move::D3,
-                Move::L2,
-                Move::B2,
-            ] {
-                let corners1 =
-                    self.solvertables.mv.corners_move[N_MOVE * corners as usize + m as usize];
-                let ud_edges1 =
-                    self.solvertables.mv.ud_edges_move[N_MOVE * ud_edges as usize + m as usize];
-                let classidx1 = self.solvertables.sy.corner_classidx[corners1 as usize];
-                let sym = self.solvertables.sy.corner_sym[corners1 as usize];
-                if self.solvertables.pr.get_corners_ud_edges_depth3(
-                    N_UD_EDGES * classidx1 as usize
-                        + self.solvertables.sy.ud_edges_conj
-                            [((ud_edges1 as usize) << 4) + sym as usize]
-                            as usize,
-                ) == depth_mod3 - 1
-                {
-                    depth += 1;
-                    corners = corners1;
-                    ud_edges = ud_edges1;
-                    depth_mod3 -= 1;
-                    break;
-                }
-            }
-        }
-        depth
+        self.solvertables.phase2_depth(corners, ud_edges)
     }
 
     /// search_phase2
@@ -412,52 +826,34 @@ impl<'a> SolverThread<'a> {
         dist: u16,
         togo_phase2: u16,
     ) -> bool {
-        {
-            let terminated = self.terminated.lock().unwrap();
-            if *terminated || self.phase2_done {
-                // println!("Search2 terminated {}", *terminated);
-                return true;
-            }
+        if self.shared.terminated.load(Ordering::Relaxed) || self.phase2_done {
+            return true;
         }
+        self.nodes += 1;
         if togo_phase2 == 0 && slice_sorted == 0 {
             // phase 2 solved, store solution
             let mut man = self.sofar_phase1.clone();
             let mut other = self.sofar_phase2.clone();
             man.append(&mut other);
-            let mut solutions = self.solutions.lock().unwrap();
-            // let lslen = (*solutions).last().unwrap().len();
-            let ls = (*solutions).last();
-            let lslen = match ls.is_some() {
-                true => ls.unwrap().len(),
-                false => 0,
-            };
-            if (*solutions).len() == 0 || lslen > man.len() {
-                if self.inv == 1 {
-                    // we solved the inverse cube
-                    man.reverse();
-                    let mut newman = Vec::new();
-                    for m in man {
-                        newman.push(ALL_MOVES[(m as usize / 3) * 3 + (2 - (m as usize) % 3)]);
-                        // R1->R3, R2->R2, R3->R1 etc.
-                    }
-                    man = newman;
+            if let Some(options) = &self.shared.enumerate {
+                // collect every solution, keep searching
+                let man = enumerate::normalize(&self.transform_solution(man));
+                if self.shared.enumerated.lock().unwrap().insert(man.clone()) && options.accepts(&man) {
+                    self.shared.solutions.lock().unwrap().push(man);
                 }
-                let mut newman = Vec::new();
-                for m in man {
-                    newman.push(
-                        ALL_MOVES[self.solvertables.sy.conj_move
-                            [N_MOVE * 16 * self.rot as usize + m as usize]],
-                    );
-                }
-                man = newman;
-                self.shortest_length[0] = man.len();
+                return true;
+            }
+            let mut solutions = self.shared.solutions.lock().unwrap();
+            // a sibling thread may have found a shorter solution meanwhile
+            if self.shared.shortest_length.load(Ordering::Relaxed) > man.len() {
+                let man = self.transform_solution(man);
+                self.shared.shortest_length.store(man.len(), Ordering::Relaxed);
                 (*solutions).push(man);
             }
 
-            if self.shortest_length[0] <= self.ret_length {
+            if self.shared.shortest_length.load(Ordering::Relaxed) <= self.limits.max_length {
                 // we have reached the target length
-                let mut terminated = self.terminated.lock().unwrap();
-                *terminated = true;
+                self.shared.terminated.store(true, Ordering::Relaxed);
             }
             self.phase2_done = true;
         } else {
@@ -540,22 +936,18 @@ impl<'a> SolverThread<'a> {
         dist: u16,
         togo_phase1: u16,
     ) -> bool {
-        {
-            let terminated = self.terminated.lock().unwrap();
-            if *terminated {
-                return true;
-            }
+        if self.shared.terminated.load(Ordering::Relaxed) {
+            return true;
         }
+        self.nodes += 1;
 
         if togo_phase1 == 0 {
             // phase 1 solved
             {
-                let solutions = self.solutions.lock().unwrap();
-                if self.start_time.elapsed() > Duration::from_secs_f32(self.timeout)
-                    && (*solutions).len() > 0
-                {
-                    let mut terminated = self.terminated.lock().unwrap();
-                    *terminated = true;
+                let nodes = self.flush_nodes();
+                let solutions = self.shared.solutions.lock().unwrap();
+                if self.budget_exhausted(nodes) && (self.limits.hard || (*solutions).len() > 0) {
+                    self.shared.terminated.store(true, Ordering::Relaxed);
                 }
             }
             // compute initial phase 2 coordinates
@@ -582,8 +974,27 @@ impl<'a> SolverThread<'a> {
                 self.cornersave = corners;
             }
 
-            // new solution must be shorter and we do not use phase 2 maneuvers with length > 11 - 1 = 10
-            let togo2_limit = min(self.shortest_length[0] - self.sofar_phase1.len(), 11) as u16;
+            // new solution must be shorter than the best solution of all threads and we do not use phase 2
+            // maneuvers with length > max_phase2_length (10 by default), except when enumerating all solutions
+            let max_phase2_length = match self.shared.enumerate {
+                Some(_) => u16::MAX as usize - 1,
+                None => self.limits.max_phase2_length as usize,
+            };
+            let togo2_limit = min(
+                self.shared
+                    .shortest_length
+                    .load(Ordering::Relaxed)
+                    .saturating_sub(self.sofar_phase1.len()),
+                max_phase2_length + 1,
+            ) as u16;
+            // with a given total length only phase 2 maneuvers of the remaining length are searched
+            let (togo2_min, togo2_limit) = match self.total_length {
+                Some(total) => {
+                    let togo2 = total - self.sofar_phase1.len() as u16;
+                    (togo2, min(togo2_limit, togo2 + 1))
+                }
+                None => (0, togo2_limit),
+            };
             if self.solvertables.pr.cornslice_depth[24 * corners as usize + slice_sorted as usize]
                 >= togo2_limit
             {
@@ -602,7 +1013,7 @@ impl<'a> SolverThread<'a> {
                 self.solvertables.em.upd_ud_edges[24 * u_edges as usize + d_edges as usize % 24];
 
             let dist2 = self.get_depth_phase2(corners, ud_edges);
-            for togo2 in dist2..togo2_limit {
+            for togo2 in max(dist2, togo2_min)..togo2_limit {
                 // do not use more than togo2_limit - 1 moves in phase 2
                 self.sofar_phase2 = Vec::new();
                 self.phase2_done = false;
@@ -616,9 +1027,10 @@ impl<'a> SolverThread<'a> {
             for m in ALL_MOVES {
                 // dist = 0 means that we are already are in the subgroup H. If there are less than 5 moves left
                 // this forces all remaining moves to be phase 2 moves. So we can forbid these at the end of phase 1
-                // and generate these moves in phase 2.
+                // and generate these moves in phase 2. When enumerating all solutions nothing is forbidden.
                 if dist == 0
                     && togo_phase1 < 5
+                    && self.shared.enumerate.is_none()
                     && [
                         Move::U,
                         Move::U2,
@@ -679,8 +1091,55 @@ impl<'a> SolverThread<'a> {
         true
     }
 
+    /// Transform a solution of the rotated/inverted cube into a solution of the cube.
+    fn transform_solution(&self, mut man: Vec<Move>) -> Vec<Move> {
+        if self.inv == 1 {
+            // we solved the inverse cube
+            man.reverse();
+            let mut newman = Vec::new();
+            for m in man {
+                newman.push(ALL_MOVES[(m as usize / 3) * 3 + (2 - (m as usize) % 3)]);
+                // R1->R3, R2->R2, R3->R1 etc.
+            }
+            man = newman;
+        }
+        let mut newman = Vec::new();
+        for m in man {
+            newman.push(
+                ALL_MOVES[self.solvertables.sy.conj_move[N_MOVE * 16 * self.rot as usize + m as usize]],
+            );
+        }
+        newman
+    }
+
+    /// Add the nodes visited since the last call to the shared node count, return the new count.
+    fn flush_nodes(&mut self) -> u64 {
+        let nodes = self.shared.nodes.fetch_add(self.nodes, Ordering::Relaxed) + self.nodes;
+        self.nodes = 0;
+        nodes
+    }
+
+    /// Check the timeout and node budgets, nodes is the number of nodes visited by all threads.
+    fn budget_exhausted(&self, nodes: u64) -> bool {
+        let timed_out = self.limits.timeout.map(|timeout| {
+            self.start_time
+                .is_some_and(|t| t.elapsed() > Duration::from_secs_f32(timeout))
+        });
+        let out_of_nodes = self.limits.max_nodes.map(|max_nodes| nodes > max_nodes);
+        self.limits.policy.exhausted(&[timed_out, out_of_nodes])
+    }
+
     /// Start solverthread to find solution.
     pub fn start(&mut self) {
+        let dist = self.init();
+        for togo1 in dist..=self.limits.max_phase1_length {
+            // iterative deepening, solution has at least dist moves
+            self.search_depth(dist, togo1);
+        }
+    }
+
+    /// Set up the rotated/inverted cube, return its distance to the subgroup H.
+    pub fn init(&mut self) -> u16 {
         let mut cb = CubieCube::default();
         let sc = &self.solvertables.sy.sc;
         if self.rot == 0 {
@@ -718,25 +1177,72 @@ impl<'a> SolverThread<'a> {
         }
         // self.co_cube = CoordCube::try_from(&cb)?; // the rotated/inverted cube in coordinate representation
         self.co_cube = CoordCube::from_cubie(&cb, &self.solvertables.sy).unwrap(); // the rotated/inverted cube in coordinate representation
-        let dist = self.get_depth_phase1() as u16;
-        for togo1 in dist..20 {
-            // iterative deepening, solution has at least dist moves
-            self.sofar_phase1 = Vec::new();
-            let _ret = self.search(
-                self.co_cube.flip,
-                self.co_cube.twist,
-                self.co_cube.slice_sorted,
-                dist,
-                togo1,
-            );
+        self.get_depth_phase1() as u16
+    }
+
+    /// Search all solutions with exactly total moves, dist is the value returned by [SolverThread::init].
+    ///
+    /// Unlike [SolverThread::search_depth] the phase depth caps of the limits are not used.
+    pub fn search_length(&mut self, dist: u16, total: u16) {
+        self.total_length = Some(total);
+        for togo1 in dist..=total {
+            self.search_depth(dist, togo1);
         }
+        self.total_length = None;
+    }
+
+    /// Search all solutions with togo1 phase 1 moves, dist is the value returned by [SolverThread::init].
+    pub fn search_depth(&mut self, dist: u16, togo1: u16) {
+        self.sofar_phase1 = Vec::new();
+        let _ret = self.search(
+            self.co_cube.flip,
+            self.co_cube.twist,
+            self.co_cube.slice_sorted,
+            dist,
+            togo1,
+        );
+        self.flush_nodes();
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::moves::Move::*;
+    use crate::scramble::scramble_from_str;
     use crate::solver::*;
+    use crate::stats::random_cubes;
+
+    /// Maneuvers with a known distance: the optimal number of moves for the superflip, the checkerboard and the
+    /// canonical maneuvers of up to three moves (all of them give different cubes), the length of the maneuver as an
+    /// upper bound of the distance for the other patterns.
+    ///
+    /// The superflip followed by U or by U D is one or two moves away from the superflip with its distance of 20
+    /// moves, so these cubes need at least 19 or 18 moves, and the solver finds solutions of these lengths.
+    fn known_distances() -> Vec<(&'static str, usize, bool)> {
+        let mut corpus = vec![
+            (Pattern::Superflip.maneuver(), 20, true),
+            ("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2 U", 19, true),
+            ("U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2 U D", 18, true),
+            (Pattern::Checkerboard.maneuver(), 6, true),
+            ("", 0, true),
+            ("R", 1, true),
+            ("F2 U'", 2, true),
+            ("U D R'", 3, true),
+            ("L2 F B2", 3, true),
+        ];
+        for pattern in [
+            Pattern::CubeInCubeInCube,
+            Pattern::Wire,
+            Pattern::CubeInCube,
+            Pattern::Python,
+            Pattern::Anaconda,
+            Pattern::Twister,
+        ] {
+            let maneuver = pattern.maneuver();
+            corpus.push((maneuver, maneuver.split_whitespace().count(), false));
+        }
+        corpus
+    }
 
     #[test]
     fn test_solve() {
@@ -754,6 +1260,193 @@ mod test {
         // println!("{:?}, ({})", solution, solution.len());
     }
 
+    #[test]
+    fn test_solve_single_threaded() {
+        let cubestring = "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF";
+        let cc = CubieCube::try_from(&FaceCube::try_from(cubestring).unwrap()).unwrap();
+        let result = solve_single_threaded(cubestring, 20, u64::MAX).unwrap();
+        assert!(result.solution.len() <= 20);
+        assert_eq!(cc.apply_moves(&result.solution), CubieCube::default());
+        // the same input gives the same solution
+        for _ in 0..2 {
+            let other = solve_single_threaded(cubestring, 20, u64::MAX).unwrap();
+            assert_eq!(other.solution, result.solution);
+        }
+        // a length of 0 is never reached, the node budget stops the search after the first solution
+        let result = solve_single_threaded(cubestring, 0, 0).unwrap();
+        assert_eq!(cc.apply_moves(&result.solution), CubieCube::default());
+        assert_eq!(
+            solve_single_threaded(cubestring, 0, 0).unwrap().solution,
+            result.solution
+        );
+        let result = solve_single_threaded(GOALSTRING, 20, 0).unwrap();
+        assert!(result.solution.is_empty());
+    }
+
+    #[test]
+    fn test_search_limits() {
+        let cubestring = "RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF";
+        let limits = SearchLimits {
+            max_length: 0,
+            max_nodes: Some(100_000),
+            ..Default::default()
+        };
+        let result = solver_single_threaded_with_limits(cubestring, GOALSTRING, &limits).unwrap();
+        assert!(result.nodes > 100_000);
+        let other = solver_single_threaded_with_limits(cubestring, GOALSTRING, &limits).unwrap();
+        assert_eq!(other.solution, result.solution);
+        assert_eq!(other.nodes, result.nodes);
+        let result = solver_with_limits(cubestring, GOALSTRING, &limits).unwrap();
+        assert!(result.nodes > 100_000);
+
+        // a hard budget stops before the first solution
+        let limits = SearchLimits {
+            max_nodes: Some(0),
+            hard: true,
+            ..limits
+        };
+        assert!(matches!(
+            solver_single_threaded_with_limits(cubestring, GOALSTRING, &limits),
+            Err(Error::NoSolution)
+        ));
+
+        // the cube is not in H, the phase 1 depth cap allows no solution
+        let limits = SearchLimits {
+            max_phase1_length: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            solver_with_limits(cubestring, GOALSTRING, &limits),
+            Err(Error::NoSolution)
+        ));
+        let result = solver_with_limits(
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB",
+            GOALSTRING,
+            &limits,
+        )
+        .unwrap();
+        assert!(result.solution.is_empty());
+
+        assert!(LimitPolicy::Any.exhausted(&[Some(false), Some(true)]));
+        assert!(!LimitPolicy::All.exhausted(&[Some(false), Some(true)]));
+        assert!(LimitPolicy::All.exhausted(&[None, Some(true)]));
+        assert!(!LimitPolicy::All.exhausted(&[None, None]));
+        assert!(!LimitPolicy::Any.exhausted(&[None, None]));
+    }
+
+    #[test]
+    fn test_solve_cubie() {
+        let limits = SearchLimits::default();
+        let start = CubieCube::try_from(
+            &FaceCube::try_from("RLLBUFUUUBDURRBBUBRLRRFDFDDLLLUDFLRRDDFRLFDBUBFFLBBDUF").unwrap(),
+        )
+        .unwrap();
+        for pattern in Pattern::ALL {
+            let result = solve_to_pattern(&start, pattern, &limits).unwrap();
+            assert!(result.solution.len() <= 20);
+            assert_eq!(start.apply_moves(&result.solution), pattern.cube());
+        }
+        let goal = FaceCube::try_from(&Pattern::Superflip.cube()).unwrap();
+        let result = solve_facecube(&FaceCube::default(), &goal, &limits).unwrap();
+        assert_eq!(
+            CubieCube::default().apply_moves(&result.solution),
+            Pattern::Superflip.cube()
+        );
+
+        let mut invalid = CubieCube::default();
+        invalid.eo[0] = 1;
+        assert!(matches!(
+            solve_cubie(&invalid, &start, &limits),
+            Err(Error::InvalidStart(_))
+        ));
+        assert!(matches!(
+            solve_cubie(&start, &invalid, &limits),
+            Err(Error::InvalidGoal(_))
+        ));
+    }
+
+    #[test]
+    fn test_known_distances() {
+        for (maneuver, distance, optimal) in known_distances() {
+            let cc = CubieCube::from(&scramble_from_str(maneuver).unwrap());
+            let limits = SearchLimits {
+                max_length: distance,
+                ..Default::default()
+            };
+            let result = solve_cubie(&cc, &CubieCube::default(), &limits).unwrap();
+            assert_eq!(cc.apply_moves(&result.solution), CubieCube::default());
+            if optimal {
+                assert_eq!(result.solution.len(), distance, "{}", maneuver);
+            } else {
+                assert!(result.solution.len() <= distance, "{}", maneuver);
+            }
+        }
+    }
+
+    #[test]
+    fn test_solutions_reach_goal() {
+        let cubes = random_cubes(12, 7);
+        for (i, cc) in cubes.iter().enumerate() {
+            // the diameter of the cube group is 20, so 20 moves are always achievable
+            for max_length in [20, 21, 24] {
+                let limits = SearchLimits {
+                    max_length,
+                    ..Default::default()
+                };
+                let result = solve_cubie(cc, &CubieCube::default(), &limits).unwrap();
+                assert!(result.solution.len() <= max_length);
+                assert_eq!(cc.apply_moves(&result.solution), CubieCube::default());
+            }
+            // a node budget gives the first solution, which may be longer but stays well below the search depth
+            let limits = SearchLimits {
+                max_length: 0,
+                max_nodes: Some(0),
+                ..Default::default()
+            };
+            let result = solve_cubie(cc, &CubieCube::default(), &limits).unwrap();
+            assert!(result.solution.len() <= 30);
+            assert_eq!(cc.apply_moves(&result.solution), CubieCube::default());
+
+            let goal = &cubes[(i + 1) % cubes.len()];
+            let result = solve_cubie(cc, goal, &SearchLimits::default()).unwrap();
+            assert!(result.solution.len() <= 20);
+            assert_eq!(cc.apply_moves(&result.solution), *goal);
+        }
+    }
+
+    #[test]
+    fn test_verify_solution() {
+        let cc = CubieCube::from(&scramble_from_str("R U2 F' D").unwrap());
+        let limits = SearchLimits {
+            self_check: true,
+            ..Default::default()
+        };
+        let result = solve_cubie(&cc, &CubieCube::default(), &limits).unwrap();
+        assert!(verify_solution(&cc, &CubieCube::default(), &result.solution).is_ok());
+        let fc = FaceCube::try_from(&cc).unwrap().to_string();
+        let result = solver_single_threaded_with_limits(&fc, GOALSTRING, &limits).unwrap();
+        assert!(verify_solution(&cc, &CubieCube::default(), &result.solution).is_ok());
+
+        let goal = Pattern::Checkerboard.cube();
+        let result = solve_cubie(&cc, &goal, &limits).unwrap();
+        assert!(verify_solution(&cc, &goal, &result.solution).is_ok());
+        assert!(matches!(
+            verify_solution(&cc, &CubieCube::default(), &result.solution),
+            Err(Error::InvalidSolution)
+        ));
+
+        let mut invalid = CubieCube::default();
+        invalid.co[0] = 1;
+        assert!(matches!(
+            verify_solution(&invalid, &goal, &[]),
+            Err(Error::InvalidStart(_))
+        ));
+        assert!(matches!(
+            verify_solution(&cc, &invalid, &[]),
+            Err(Error::InvalidGoal(_))
+        ));
+    }
+
     #[test]
     fn test_solver() {
         let result = solver(
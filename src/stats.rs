@@ -0,0 +1,328 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::facelet::FaceCube;
+use crate::moves::Move;
+use crate::scramble::scramble_to_str;
+use crate::solver::{self, SearchLimits, GOALSTRING};
+
+/// Options of [run_stats].
+///
+/// * `cubes`: Number of random cubes to solve.
+/// * `seed`: Seed of the random number generator, the same seed gives the same cubes.
+/// * `limits`: The [SearchLimits] of every solve.
+/// * `single_threaded`: Solve with [solver::solver_single_threaded_with_limits], which gives reproducible
+///   solutions and node counts when the limits have no timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatsOptions {
+    pub cubes: usize,
+    pub seed: u64,
+    pub limits: SearchLimits,
+    pub single_threaded: bool,
+}
+
+impl Default for StatsOptions {
+    fn default() -> Self {
+        Self {
+            cubes: 100,
+            seed: 0,
+            limits: SearchLimits::default(),
+            single_threaded: false,
+        }
+    }
+}
+
+/// The result of solving one cube, `solution` is None if no solution was found within the limits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolveRecord {
+    pub cube: FaceCube,
+    pub solution: Option<Vec<Move>>,
+    pub solve_time: Duration,
+    pub nodes: u64,
+}
+
+/// Solve times at the 50th, 90th and 99th percentile and the longest solve time.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TimePercentiles {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// Statistics over many solves.
+///
+/// * `histogram`: Number of solutions for each solution length.
+/// * `failures`: Number of cubes without a solution within the limits.
+/// * `mean_length`: Mean length of the solutions found.
+/// * `within_max_length`: Fraction of all cubes with a solution of at most `limits.max_length` moves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsReport {
+    pub options: StatsOptions,
+    pub histogram: BTreeMap<usize, usize>,
+    pub failures: usize,
+    pub mean_length: f64,
+    pub time_percentiles: TimePercentiles,
+    pub mean_nodes: f64,
+    pub within_max_length: f64,
+    pub records: Vec<SolveRecord>,
+}
+
+impl StatsReport {
+    /// Compute the statistics of the records of solves with options.
+    pub fn from_records(options: StatsOptions, records: Vec<SolveRecord>) -> Self {
+        let mut histogram = BTreeMap::new();
+        for solution in records.iter().filter_map(|r| r.solution.as_ref()) {
+            *histogram.entry(solution.len()).or_insert(0) += 1;
+        }
+        let solved: usize = histogram.values().sum();
+        let total_length: usize = histogram.iter().map(|(len, n)| len * n).sum();
+        let within: usize = histogram
+            .range(..=options.limits.max_length)
+            .map(|(_, n)| n)
+            .sum();
+        let mut times: Vec<Duration> = records.iter().map(|r| r.solve_time).collect();
+        times.sort();
+        let total_nodes: u64 = records.iter().map(|r| r.nodes).sum();
+        Self {
+            options,
+            failures: records.len() - solved,
+            mean_length: ratio(total_length as f64, solved),
+            time_percentiles: TimePercentiles {
+                p50: percentile(&times, 50),
+                p90: percentile(&times, 90),
+                p99: percentile(&times, 99),
+                max: times.last().copied().unwrap_or_default(),
+            },
+            mean_nodes: ratio(total_nodes as f64, records.len()),
+            within_max_length: ratio(within as f64, records.len()),
+            histogram,
+            records,
+        }
+    }
+
+    /// Give the records as CSV with the columns `cube,length,solution,solve_time_us,nodes`. The length and the
+    /// solution are empty for a cube without a solution.
+    pub fn to_csv(&self) -> Result<String, Error> {
+        let mut csv = String::from("cube,length,solution,solve_time_us,nodes\n");
+        for r in &self.records {
+            let (length, solution) = match &r.solution {
+                Some(s) => (s.len().to_string(), scramble_to_str(s)?),
+                None => (String::new(), String::new()),
+            };
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                r.cube,
+                length,
+                solution,
+                r.solve_time.as_micros(),
+                r.nodes
+            ));
+        }
+        Ok(csv)
+    }
+}
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = &self.time_percentiles;
+        writeln!(
+            f,
+            "Cubes: {} (seed {})",
+            self.records.len(),
+            self.options.seed
+        )?;
+        writeln!(f, "Solution lengths:")?;
+        let most = self.histogram.values().copied().max().unwrap_or(0);
+        for (len, n) in &self.histogram {
+            let bar = "#".repeat((n * 40).div_ceil(most.max(1)));
+            writeln!(f, "{:>4}: {:>6} {}", len, n, bar)?;
+        }
+        if self.failures > 0 {
+            writeln!(f, "No solution: {}", self.failures)?;
+        }
+        writeln!(f, "Mean length: {:.2}", self.mean_length)?;
+        writeln!(
+            f,
+            "Within {} moves: {:.1}%",
+            self.options.limits.max_length,
+            self.within_max_length * 100.
+        )?;
+        writeln!(
+            f,
+            "Solve time: p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+            t.p50, t.p90, t.p99, t.max
+        )?;
+        write!(f, "Mean nodes: {:.0}", self.mean_nodes)
+    }
+}
+
+fn ratio(a: f64, n: usize) -> f64 {
+    if n == 0 {
+        0.
+    } else {
+        a / n as f64
+    }
+}
+
+/// The p-th percentile of the sorted times (nearest rank).
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::default();
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Generate n random cubes with [CubieCube::randomize_with], the same seed gives the same cubes.
+pub fn random_cubes(n: usize, seed: u64) -> Vec<CubieCube> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| {
+            let mut cc = CubieCube::default();
+            cc.randomize_with(&mut rng);
+            cc
+        })
+        .collect()
+}
+
+/// Solve `options.cubes` seeded random cubes and give the statistics of the solves.
+///
+/// A cube without a solution within the limits counts as a failure, other errors stop the run.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::solver::SearchLimits;
+/// use kociemba::stats::{run_stats, StatsOptions};
+///
+/// fn main() {
+///     let options = StatsOptions {
+///         cubes: 2,
+///         limits: SearchLimits {
+///             max_length: 22,
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     };
+///     let report = run_stats(&options).unwrap();
+///     println!("{}", report);
+/// }
+/// ```
+pub fn run_stats(options: &StatsOptions) -> Result<StatsReport, Error> {
+    let mut records = Vec::with_capacity(options.cubes);
+    for cc in random_cubes(options.cubes, options.seed) {
+        let cube = FaceCube::try_from(&cc)?;
+        let result = if options.single_threaded {
+            solver::solver_single_threaded_with_limits(
+                &cube.to_string(),
+                GOALSTRING,
+                &options.limits,
+            )
+        } else {
+            solver::solve_cubie(&cc, &CubieCube::default(), &options.limits)
+        };
+        let record = match result {
+            Ok(result) => SolveRecord {
+                cube,
+                solution: Some(result.solution),
+                solve_time: result.solve_time,
+                nodes: result.nodes,
+            },
+            Err(Error::NoSolution) => SolveRecord {
+                cube,
+                solution: None,
+                solve_time: Duration::default(),
+                nodes: 0,
+            },
+            Err(e) => return Err(e),
+        };
+        records.push(record);
+    }
+    Ok(StatsReport::from_records(*options, records))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::moves::Move::*;
+    use crate::stats::*;
+
+    #[test]
+    fn test_random_cubes() {
+        let cubes = random_cubes(5, 7);
+        assert_eq!(cubes, random_cubes(5, 7));
+        assert_ne!(cubes, random_cubes(5, 8));
+        for cc in cubes {
+            assert!(cc.verify().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_from_records() {
+        let record = |solution: Option<Vec<Move>>, ms| SolveRecord {
+            cube: FaceCube::default(),
+            solution,
+            solve_time: Duration::from_millis(ms),
+            nodes: 10,
+        };
+        let options = StatsOptions {
+            limits: SearchLimits {
+                max_length: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let records = vec![
+            record(Some(vec![R, U]), 4),
+            record(Some(vec![R, U, F]), 1),
+            record(Some(vec![R3, U3]), 3),
+            record(None, 2),
+        ];
+        let report = StatsReport::from_records(options, records);
+        assert_eq!(report.histogram, BTreeMap::from([(2, 2), (3, 1)]));
+        assert_eq!(report.failures, 1);
+        assert!((report.mean_length - 7. / 3.).abs() < 1e-9);
+        assert_eq!(report.within_max_length, 0.5);
+        assert_eq!(report.mean_nodes, 10.);
+        assert_eq!(report.time_percentiles.p50, Duration::from_millis(2));
+        assert_eq!(report.time_percentiles.max, Duration::from_millis(4));
+        let csv = report.to_csv().unwrap();
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.lines().nth(1).unwrap().ends_with(",2,R U,4000,10"));
+        assert!(csv.lines().nth(4).unwrap().ends_with(",,,2000,10"));
+    }
+
+    #[test]
+    fn test_run_stats() {
+        let options = StatsOptions {
+            cubes: 3,
+            seed: 1,
+            limits: SearchLimits {
+                max_length: 23,
+                ..Default::default()
+            },
+            single_threaded: true,
+        };
+        let report = run_stats(&options).unwrap();
+        assert_eq!(report.records.len(), 3);
+        assert_eq!(report.within_max_length, 1.);
+        for (r, cc) in report.records.iter().zip(random_cubes(3, 1)) {
+            assert_eq!(
+                cc.apply_moves(r.solution.as_ref().unwrap()),
+                CubieCube::default()
+            );
+            assert!(r.nodes > 0);
+        }
+        // the single threaded search without timeout is reproducible, only the solve times differ
+        let again = run_stats(&options).unwrap();
+        assert_eq!(again.histogram, report.histogram);
+        for (a, b) in again.records.iter().zip(&report.records) {
+            assert_eq!((&a.solution, a.nodes), (&b.solution, b.nodes));
+        }
+    }
+}
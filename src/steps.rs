@@ -0,0 +1,494 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::*;
+use crate::cross::D_SLOTS;
+use crate::cubie::{move_cubes, Corner, Corner::*, CubieCube, Edge, Edge::*};
+use crate::error::Error;
+use crate::moves::{self, Move, Move::*};
+
+/// Number of locations of a corner (8 positions * 3 orientations) or an edge (12 positions * 2 orientations).
+const N_LOCATION: usize = 24;
+/// Maximal number of pieces in a pruning table, a table has 24^4 = 331776 entries.
+const GROUP_SIZE: usize = 4;
+
+/// The moves of the L, U, R and D faces, which keep the edge orientation of ZZ.
+pub const LURD_MOVES: [Move; 12] = [U, U2, U3, R, R2, R3, D, D2, D3, L, L2, L3];
+/// The moves of the L, U and R faces, the ZZ F2L after EOLine.
+pub const LUR_MOVES: [Move; 9] = [U, U2, U3, R, R2, R3, L, L2, L3];
+/// The moves of the R and U faces, e.g. for the Roux second block.
+pub const RU_MOVES: [Move; 6] = [U, U2, U3, R, R2, R3];
+/// All moves except the D face, which keep a solved D cross in place for the F2L pairs.
+pub const F2L_MOVES: [Move; 15] = [U, U2, U3, R, R2, R3, F, F2, F3, L, L2, L3, B, B2, B3];
+
+/// A sub-goal of a speedsolving method: the corners and edges which have to be solved (in place and oriented) and
+/// whether all edges and all corners have to be oriented.
+///
+/// More pieces may be added to the predefined goals, e.g. the F2L pairs which are already solved.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct StepGoal {
+    pub corners: Vec<Corner>,
+    pub edges: Vec<Edge>,
+    pub orient_edges: bool,
+    pub orient_corners: bool,
+}
+
+impl StepGoal {
+    /// ZZ EOLine: all edges oriented and the DF and DB edges solved.
+    pub fn eoline() -> Self {
+        Self {
+            edges: vec![DF, DB],
+            orient_edges: true,
+            ..Default::default()
+        }
+    }
+
+    /// ZZ EOCross: all edges oriented and the D cross solved.
+    pub fn eocross() -> Self {
+        Self {
+            edges: vec![DR, DF, DL, DB],
+            orient_edges: true,
+            ..Default::default()
+        }
+    }
+
+    /// Roux first block: the 1x2x3 block on the D layer of the L face.
+    pub fn first_block() -> Self {
+        Self {
+            corners: vec![DLF, DBL],
+            edges: vec![DL, FL, BL],
+            ..Default::default()
+        }
+    }
+
+    /// Roux second block: the 1x2x3 block on the D layer of the R face with the first block kept.
+    pub fn second_block() -> Self {
+        Self {
+            corners: vec![DLF, DBL, DFR, DRB],
+            edges: vec![DL, FL, BL, DR, FR, BR],
+            ..Default::default()
+        }
+    }
+
+    /// CFOP F2L pair: the D cross and the corner and the edge of the slot, the slot is an index into [D_SLOTS].
+    pub fn f2l_pair(slot: usize) -> Self {
+        let (corner, edge) = D_SLOTS[slot];
+        Self {
+            corners: vec![corner],
+            edges: vec![DR, DF, DL, DB, edge],
+            ..Default::default()
+        }
+    }
+
+    /// CFOP OLL: the first two layers kept and the last layer oriented.
+    pub fn oll() -> Self {
+        Self {
+            corners: vec![DFR, DLF, DBL, DRB],
+            edges: vec![DR, DF, DL, DB, FR, FL, BL, BR],
+            orient_edges: true,
+            orient_corners: true,
+        }
+    }
+
+    /// CFOP PLL: the whole cube solved.
+    pub fn pll() -> Self {
+        Self {
+            corners: vec![URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB],
+            edges: vec![UR, UF, UL, UB, DR, DF, DL, DB, FR, FL, BL, BR],
+            ..Default::default()
+        }
+    }
+
+    /// Check if the goal is reached on the cube.
+    pub fn is_solved(&self, cc: &CubieCube) -> bool {
+        self.corners
+            .iter()
+            .all(|c| cc.corner_location(*c) == (*c, 0))
+            && self.edges.iter().all(|e| cc.edge_location(*e) == (*e, 0))
+            && (!self.orient_edges || cc.get_flip() == 0)
+            && (!self.orient_corners || cc.get_twist() == 0)
+    }
+}
+
+/// All optimal solutions of a step.
+///
+/// * `length`: The number of moves of the optimal solutions.
+/// * `solutions`: All optimal solutions, successive moves on opposite faces are given in one order only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepSolutions {
+    pub length: usize,
+    pub solutions: Vec<Vec<Move>>,
+}
+
+/// The search state: the flip and twist coordinates and the locations of the pieces of the goal, corners first.
+#[derive(Debug, Clone, Copy)]
+struct StepState {
+    flip: usize,
+    twist: usize,
+    locations: [u8; 20],
+}
+
+/// Solver for a [StepGoal] with a set of moves.
+///
+/// The pieces of the goal are tracked by their locations. The pruning tables are created by breadth-first searches
+/// over groups of up to four pieces and over the flip and twist coordinates for the allowed moves when the solver is
+/// created, so a solver should be reused for many cubes.
+pub struct StepSolver {
+    pub goal: StepGoal,
+    pub moves: Vec<Move>,
+    corner_move: Vec<u8>,
+    edge_move: Vec<u8>,
+    flip_move: Vec<u16>,
+    flip_distance: Vec<u8>,
+    twist_move: Vec<u16>,
+    twist_distance: Vec<u8>,
+    /// The indices of the pieces of each group and its distance table.
+    groups: Vec<(Vec<usize>, Vec<u8>)>,
+}
+
+impl StepSolver {
+    /// Create the tables of a solver for goal with the allowed moves.
+    pub fn new(goal: StepGoal, moves: &[Move]) -> Result<Self, Error> {
+        // each piece once, so there are at most 20 pieces
+        if (1..goal.corners.len()).any(|i| goal.corners[..i].contains(&goal.corners[i])) {
+            return Err(Error::InvalidCorner);
+        }
+        if (1..goal.edges.len()).any(|i| goal.edges[..i].contains(&goal.edges[i])) {
+            return Err(Error::InvalidEdge);
+        }
+        let n_pieces = goal.corners.len() + goal.edges.len();
+        let mut solver = Self {
+            goal,
+            moves: moves.to_vec(),
+            corner_move: location_move(true),
+            edge_move: location_move(false),
+            flip_move: moves::move_flip()?,
+            flip_distance: Vec::new(),
+            twist_move: moves::move_twist()?,
+            twist_distance: Vec::new(),
+            groups: Vec::new(),
+        };
+        if solver.goal.orient_edges {
+            solver.flip_distance = solver.breadth_first(N_FLIP, 0, |flip, m| {
+                solver.flip_move[N_MOVE * flip + m as usize] as usize
+            });
+        }
+        if solver.goal.orient_corners {
+            solver.twist_distance = solver.breadth_first(N_TWIST, 0, |twist, m| {
+                solver.twist_move[N_MOVE * twist + m as usize] as usize
+            });
+        }
+        let solved = solver.state(&CubieCube::default());
+        for group in piece_groups(n_pieces) {
+            let n = N_LOCATION.pow(group.len() as u32);
+            let distance = solver.breadth_first(n, group_index(&group, &solved), |idx, m| {
+                solver.group_move(&group, idx, m)
+            });
+            solver.groups.push((group, distance));
+        }
+        Ok(solver)
+    }
+
+    fn is_corner(&self, piece: usize) -> bool {
+        piece < self.goal.corners.len()
+    }
+
+    fn location_move(&self, piece: usize, location: u8, m: Move) -> u8 {
+        let table = if self.is_corner(piece) {
+            &self.corner_move
+        } else {
+            &self.edge_move
+        };
+        table[N_MOVE * location as usize + m as usize]
+    }
+
+    /// Apply a move to the index of the locations of the pieces of a group.
+    fn group_move(&self, group: &[usize], idx: usize, m: Move) -> usize {
+        let mut moved = 0;
+        for (i, piece) in group.iter().enumerate().rev() {
+            let location = (idx / N_LOCATION.pow(i as u32)) % N_LOCATION;
+            moved = N_LOCATION * moved + self.location_move(*piece, location as u8, m) as usize;
+        }
+        moved
+    }
+
+    /// Distances from the state solved for all n states with the allowed moves.
+    ///
+    /// States which cannot be reached with the allowed moves keep the distance [u8::MAX].
+    fn breadth_first(
+        &self,
+        n: usize,
+        solved: usize,
+        moved: impl Fn(usize, Move) -> usize,
+    ) -> Vec<u8> {
+        let mut distance = vec![u8::MAX; n];
+        distance[solved] = 0;
+        let mut frontier = vec![solved];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for idx in frontier {
+                for m in &self.moves {
+                    let idx1 = moved(idx, *m);
+                    if distance[idx1] == u8::MAX {
+                        distance[idx1] = depth + 1;
+                        next.push(idx1);
+                    }
+                }
+            }
+            frontier = next;
+            depth += 1;
+        }
+        distance
+    }
+
+    fn state(&self, cc: &CubieCube) -> StepState {
+        let mut locations = [0; 20];
+        let corners = self.goal.corners.iter().map(|c| {
+            let (p, o) = cc.corner_location(*c);
+            3 * p as u8 + o
+        });
+        let edges = self.goal.edges.iter().map(|e| {
+            let (p, o) = cc.edge_location(*e);
+            2 * p as u8 + o
+        });
+        for (l, location) in locations.iter_mut().zip(corners.chain(edges)) {
+            *l = location;
+        }
+        StepState {
+            flip: if self.goal.orient_edges {
+                cc.get_flip() as usize
+            } else {
+                0
+            },
+            twist: if self.goal.orient_corners {
+                cc.get_twist() as usize
+            } else {
+                0
+            },
+            locations,
+        }
+    }
+
+    fn apply_move(&self, state: &StepState, m: Move) -> StepState {
+        let mut moved = *state;
+        if self.goal.orient_edges {
+            moved.flip = self.flip_move[N_MOVE * state.flip + m as usize] as usize;
+        }
+        if self.goal.orient_corners {
+            moved.twist = self.twist_move[N_MOVE * state.twist + m as usize] as usize;
+        }
+        let n_pieces = self.goal.corners.len() + self.goal.edges.len();
+        for (i, l) in moved.locations[..n_pieces].iter_mut().enumerate() {
+            *l = self.location_move(i, *l, m);
+        }
+        moved
+    }
+
+    /// Lower bound of the number of moves to reach the goal, [u8::MAX] if it cannot be reached.
+    fn heuristic(&self, state: &StepState) -> u8 {
+        let mut dist = if self.goal.orient_edges {
+            self.flip_distance[state.flip]
+        } else {
+            0
+        };
+        if self.goal.orient_corners {
+            dist = dist.max(self.twist_distance[state.twist]);
+        }
+        for (group, distance) in &self.groups {
+            dist = dist.max(distance[group_index(group, state)]);
+        }
+        dist
+    }
+
+    fn search(
+        &self,
+        state: &StepState,
+        togo: usize,
+        maneuver: &mut Vec<Move>,
+        solutions: &mut Vec<Vec<Move>>,
+    ) {
+        if togo == 0 {
+            solutions.push(maneuver.clone());
+            return;
+        }
+        for m in &self.moves {
+            if let Some(last) = maneuver.last() {
+                // successive moves on opposite faces only in one order
+                if [0, 3].contains(&(*last as i8 / 3 - *m as i8 / 3)) {
+                    continue;
+                }
+            }
+            let state1 = self.apply_move(state, *m);
+            if (self.heuristic(&state1) as usize) < togo {
+                maneuver.push(*m);
+                self.search(&state1, togo - 1, maneuver, solutions);
+                maneuver.pop();
+            }
+        }
+    }
+
+    /// Give all optimal solutions of the goal with at most max_length moves.
+    ///
+    /// Gives [Error::NoSolution] if the goal cannot be reached with max_length of the allowed moves.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use kociemba::constants::ALL_MOVES;
+    /// use kociemba::cubie::CubieCube;
+    /// use kociemba::scramble::scramble_from_str;
+    /// use kociemba::steps::{StepGoal, StepSolver};
+    ///
+    /// fn main() {
+    ///     let solver = StepSolver::new(StepGoal::eoline(), &ALL_MOVES).unwrap();
+    ///     let cc = CubieCube::from(&scramble_from_str("R U R' U' F2 D L2 B' R2 U").unwrap());
+    ///     let eolines = solver.solve(&cc, 10).unwrap();
+    ///     println!("{} moves: {:?}", eolines.length, eolines.solutions);
+    /// }
+    /// ```
+    pub fn solve(&self, cc: &CubieCube, max_length: usize) -> Result<StepSolutions, Error> {
+        cc.verify()?;
+        let state = self.state(cc);
+        let mut solutions = Vec::new();
+        let mut togo = self.heuristic(&state) as usize;
+        while solutions.is_empty() {
+            if togo > max_length {
+                return Err(Error::NoSolution);
+            }
+            self.search(&state, togo, &mut Vec::new(), &mut solutions);
+            togo += 1;
+        }
+        Ok(StepSolutions {
+            length: togo - 1,
+            solutions,
+        })
+    }
+}
+
+/// The index of the locations of the pieces of a group into its distance table.
+fn group_index(group: &[usize], state: &StepState) -> usize {
+    group
+        .iter()
+        .rev()
+        .fold(0, |idx, p| N_LOCATION * idx + state.locations[*p] as usize)
+}
+
+/// Split n pieces into groups of up to four for the pruning tables, the last group is filled up with the first
+/// pieces.
+fn piece_groups(n: usize) -> Vec<Vec<usize>> {
+    (0..n)
+        .step_by(GROUP_SIZE)
+        .map(|start| (start..start + GROUP_SIZE.min(n)).map(|p| p % n).collect())
+        .collect()
+}
+
+/// Move table for the location of a corner or an edge, the location is 3 * position + orientation for a corner and
+/// 2 * position + orientation for an edge.
+fn location_move(corner: bool) -> Vec<u8> {
+    let mc = move_cubes();
+    let mut table = vec![0; N_LOCATION * N_MOVE];
+    for location in 0..N_LOCATION {
+        for m in 0..N_MOVE {
+            table[N_MOVE * location + m] = if corner {
+                let (c, co) = (ALL_CORNERS[location / 3], location as u8 % 3);
+                let q = mc[m].cp.iter().position(|p| *p == c).unwrap();
+                3 * q as u8 + (co + mc[m].co[q]) % 3
+            } else {
+                let (e, eo) = (ALL_EDGES[location / 2], location as u8 % 2);
+                let r = mc[m].ep.iter().position(|p| *p == e).unwrap();
+                2 * r as u8 + (eo + mc[m].eo[r]) % 2
+            };
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use crate::steps::*;
+
+    #[test]
+    fn test_eoline() {
+        let solver = StepSolver::new(StepGoal::eoline(), &ALL_MOVES).unwrap();
+        let cc = CubieCube::from(&vec![F]);
+        let eolines = solver.solve(&cc, 10).unwrap();
+        assert_eq!(eolines.length, 1);
+        assert_eq!(eolines.solutions, vec![vec![F3]]);
+
+        let cc = CubieCube::from(&vec![R, U, F3, D, B, L2]);
+        let eolines = solver.solve(&cc, 10).unwrap();
+        assert!(eolines.length <= 6);
+        for s in &eolines.solutions {
+            assert_eq!(s.len(), eolines.length);
+            assert!(solver.goal.is_solved(&cc.apply_moves(s)));
+        }
+
+        // the edge orientation cannot be changed without F and B
+        let solver = StepSolver::new(StepGoal::eocross(), &LURD_MOVES).unwrap();
+        assert!(solver.solve(&CubieCube::from(&vec![F]), 10).is_err());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let solver = StepSolver::new(StepGoal::first_block(), &ALL_MOVES).unwrap();
+        // U and R do not move the pieces of the first block
+        let cc = CubieCube::from(&vec![R, U, L3]);
+        assert_eq!(solver.solve(&cc, 10).unwrap().solutions, vec![vec![L]]);
+
+        let solver = StepSolver::new(StepGoal::second_block(), &RU_MOVES).unwrap();
+        let cc = CubieCube::from(&vec![R, U2, R3, U3]);
+        let blocks = solver.solve(&cc, 10).unwrap();
+        assert_eq!(blocks.length, 3);
+        for s in &blocks.solutions {
+            assert!(solver.goal.is_solved(&cc.apply_moves(s)));
+        }
+
+        let goal = StepGoal {
+            corners: vec![DLF, DLF],
+            ..Default::default()
+        };
+        assert!(StepSolver::new(goal, &ALL_MOVES).is_err());
+    }
+
+    #[test]
+    fn test_f2l_pair() {
+        let solver = StepSolver::new(StepGoal::f2l_pair(0), &F2L_MOVES).unwrap();
+        let cc = CubieCube::from(&vec![R, U, R3]);
+        let pairs = solver.solve(&cc, 10).unwrap();
+        assert_eq!(pairs.solutions, vec![vec![R, U3, R3]]);
+        assert!(solver.goal.is_solved(&cc.apply_moves(&pairs.solutions[0])));
+        // the pair in the back right slot
+        assert!(StepGoal::f2l_pair(3).is_solved(&cc));
+    }
+
+    #[test]
+    fn test_last_layer() {
+        // Sune
+        let cc = CubieCube::from(&vec![R, U2, R3, U3, R, U3, R3]);
+        let solver = StepSolver::new(StepGoal::oll(), &ALL_MOVES).unwrap();
+        let olls = solver.solve(&cc, 10).unwrap();
+        assert_eq!(olls.length, 7);
+        assert!(olls.solutions.contains(&vec![R, U, R3, U, R, U2, R3]));
+        for s in &olls.solutions {
+            assert!(solver.goal.is_solved(&cc.apply_moves(s)));
+        }
+
+        // Ua perm
+        let cc = CubieCube::from(&vec![R2, U3, R3, U3, R, U, R, U, R, U3, R]);
+        let solver = StepSolver::new(StepGoal::pll(), &ALL_MOVES).unwrap();
+        let plls = solver.solve(&cc, 11).unwrap();
+        assert_eq!(plls.length, 9);
+        assert!(solver.goal.is_solved(&cc.apply_moves(&plls.solutions[0])));
+    }
+
+    #[test]
+    fn test_opposite_faces() {
+        let goal = StepGoal {
+            edges: vec![UF, DF],
+            ..Default::default()
+        };
+        let solver = StepSolver::new(goal, &ALL_MOVES).unwrap();
+        // U and D commute, the solution is given in one order only
+        let cc = CubieCube::from(&vec![U, D]);
+        assert_eq!(solver.solve(&cc, 10).unwrap().solutions, vec![vec![U3, D3]]);
+    }
+}
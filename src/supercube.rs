@@ -0,0 +1,276 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use crate::constants::{ALL_MOVES, N_MOVE, N_SYM};
+use crate::cubie::CubieCube;
+use crate::error::Error;
+use crate::facelet::FaceCube;
+use crate::moves::{merge_moves, Move, Move::*};
+use crate::solver::{self, SearchLimits, SoutionResult};
+use crate::symmetries;
+
+/// Number of center orientations: 4 for each of the 6 centers.
+const N_CENTERS: usize = 4096;
+
+lazy_static! {
+    /// For each center orientation the shortest known maneuver which gives it without moving the cubies.
+    static ref CENTER_MANEUVERS: Vec<Option<Vec<Move>>> = center_maneuvers();
+}
+
+/// A cube with oriented centers, e.g. a supercube or a picture cube.
+///
+/// `centers[f]` is the number of clockwise quarter turns of the center of face f from its solved orientation, the
+/// faces in the order U, R, F, D, L, B.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SuperCube {
+    pub cube: CubieCube,
+    pub centers: [u8; 6],
+}
+
+impl From<&Vec<Move>> for SuperCube {
+    fn from(moves: &Vec<Move>) -> Self {
+        SuperCube::default().apply_moves(moves)
+    }
+}
+
+impl TryFrom<&str> for SuperCube {
+    type Error = Error;
+
+    /// Parse a facelet string of 54 facelets followed by 6 digits 0..3, the orientations of the U, R, F, D, L and B
+    /// centers, e.g. `UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB000000` for the solved cube.
+    fn try_from(cube_string: &str) -> Result<Self, Self::Error> {
+        if cube_string.len() != 60 || !cube_string.is_ascii() {
+            return Err(Error::InvalidFaceletString);
+        }
+        let (facelets, digits) = cube_string.split_at(54);
+        let cube = CubieCube::try_from(&FaceCube::try_from(facelets)?)?;
+        let mut centers = [0; 6];
+        for (c, d) in centers.iter_mut().zip(digits.chars()) {
+            *c = d.to_digit(4).ok_or(Error::InvalidCenterOrientation)? as u8;
+        }
+        Ok(SuperCube { cube, centers })
+    }
+}
+
+impl fmt::Display for SuperCube {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fc = FaceCube::try_from(&self.cube).map_err(|_| fmt::Error)?;
+        write!(f, "{}", fc)?;
+        for c in self.centers {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl SuperCube {
+    /// Applies a move to the current state, the center of the face turns with the layer.
+    pub fn apply_move(self, m: Move) -> Self {
+        let mut centers = self.centers;
+        centers[m as usize / 3] = (centers[m as usize / 3] + m as u8 % 3 + 1) % 4;
+        Self {
+            cube: self.cube.apply_move(m),
+            centers,
+        }
+    }
+
+    /// Applies the sequence of moves to the current state.
+    pub fn apply_moves(&self, moves: &[Move]) -> Self {
+        moves.iter().fold(*self, |acc, &m| acc.apply_move(m))
+    }
+
+    /// Check if the cube is valid.
+    ///
+    /// A quarter turn twists one center and is an odd permutation of the corners, so the sum of the center twists
+    /// is odd exactly for an odd corner permutation.
+    pub fn verify(&self) -> Result<bool, Error> {
+        self.cube.verify()?;
+        if self.centers.iter().any(|c| *c > 3) {
+            return Err(Error::InvalidCenterOrientation);
+        }
+        let twists: u8 = self.centers.iter().sum();
+        if twists.is_multiple_of(2) != self.cube.corner_parity() {
+            return Err(Error::InvalidCenterOrientation);
+        }
+        Ok(true)
+    }
+
+    /// Check if the cubies are solved and the centers are oriented.
+    pub fn is_solved(&self) -> bool {
+        *self == SuperCube::default()
+    }
+}
+
+/// Give the center orientations as index into [CENTER_MANEUVERS].
+fn center_index(centers: &[u8; 6]) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .map(|(f, c)| (*c as usize % 4) << (2 * f))
+        .sum()
+}
+
+/// Maneuvers which do not move the corners and edges but turn centers: the U center by 180°, the R and L centers
+/// by 180°, the U center clockwise and the L center counterclockwise, and the U and D centers clockwise.
+#[rustfmt::skip]
+const CENTER_GENERATORS: [&[Move]; 4] = [
+    &[R, L, U, R3, L3, U2, L, R, U, L3, R3, U2],
+    &[R2, U2, F2, B2, D2, L2, D2, B2, F2, U2],
+    &[L3, U3, D3, L3, D2, U, D, B3, F, R3, L, U, R, L3, F3, B, D2, L],
+    &[B3, U3, B, D3, F3, B, U2, F3, B3, U3, L, R3, F2, L, R, B2, R2, D, B2, L2, R2, D, B3, U, B],
+];
+
+/// Maneuvers which only twist centers, the shortest combinations of generators for each center orientation.
+///
+/// The generators are the [CENTER_GENERATORS] conjugated by the 48 symmetries of the cube.
+fn center_maneuvers() -> Vec<Option<Vec<Move>>> {
+    let conj_move = symmetries::conj_move();
+    let mut generators = Vec::new();
+    for g in CENTER_GENERATORS {
+        for s in 0..N_SYM {
+            let maneuver: Vec<Move> = g
+                .iter()
+                .map(|m| ALL_MOVES[conj_move[N_MOVE * s + *m as usize]])
+                .collect();
+            if !generators.contains(&maneuver) {
+                generators.push(maneuver);
+            }
+        }
+    }
+
+    let mut maneuvers: Vec<Option<Vec<Move>>> = vec![None; N_CENTERS];
+    maneuvers[0] = Some(Vec::new());
+    // Dijkstra, the length of a maneuver is the number of moves after merging
+    let mut queue = BinaryHeap::from([(Reverse(0), [0u8; 6])]);
+    while let Some((Reverse(len), centers)) = queue.pop() {
+        let maneuver = maneuvers[center_index(&centers)].clone().unwrap();
+        if maneuver.len() < len {
+            continue;
+        }
+        for generator in &generators {
+            let next = SuperCube {
+                centers,
+                ..Default::default()
+            }
+            .apply_moves(generator)
+            .centers;
+            let idx = center_index(&next);
+            let candidate = merge_moves(&[maneuver.as_slice(), generator].concat());
+            if maneuvers[idx]
+                .as_ref()
+                .is_none_or(|m| m.len() > candidate.len())
+            {
+                queue.push((Reverse(candidate.len()), next));
+                maneuvers[idx] = Some(candidate);
+            }
+        }
+    }
+    maneuvers
+}
+
+/// Give a maneuver which does not move the corners and edges and turns the centers with the orientations centers
+/// back to their solved orientation.
+///
+/// Gives [Error::InvalidCenterOrientation] if the sum of the center twists is odd, which is not possible with
+/// solved corners and edges.
+pub fn center_fix(centers: &[u8; 6]) -> Result<Vec<Move>, Error> {
+    let inverse = centers.map(|c| (4 - c % 4) % 4);
+    CENTER_MANEUVERS[center_index(&inverse)]
+        .clone()
+        .ok_or(Error::InvalidCenterOrientation)
+}
+
+/// Solve a cube with oriented centers within limits.
+///
+/// The corners and edges are solved with the two-phase algorithm, then the centers are fixed with [center_fix].
+/// Moves of the same face at the junction are merged.
+///
+/// # Examples
+/// ```rust
+/// use kociemba::scramble::scramble_from_str;
+/// use kociemba::solver::SearchLimits;
+/// use kociemba::supercube::{solve_supercube, SuperCube};
+///
+/// fn main() {
+///     let cube = SuperCube::from(&scramble_from_str("R U R' U' F2 D L2").unwrap());
+///     let result = solve_supercube(&cube, &SearchLimits::default()).unwrap();
+///     assert!(cube.apply_moves(&result.solution).is_solved());
+/// }
+/// ```
+pub fn solve_supercube(cube: &SuperCube, limits: &SearchLimits) -> Result<SoutionResult, Error> {
+    cube.verify()?;
+    let result = solver::solve_cubie(&cube.cube, &CubieCube::default(), limits)?;
+    let centers = cube.apply_moves(&result.solution).centers;
+    let solution = merge_moves(&[result.solution, center_fix(&centers)?].concat());
+    Ok(SoutionResult { solution, ..result })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::supercube::*;
+
+    #[test]
+    fn test_center_generators() {
+        for g in CENTER_GENERATORS {
+            assert_eq!(CubieCube::default().apply_moves(g), CubieCube::default());
+        }
+        let centers = |g: &[Move]| SuperCube::default().apply_moves(g).centers;
+        assert_eq!(centers(CENTER_GENERATORS[0]), [2, 0, 0, 0, 0, 0]);
+        assert_eq!(centers(CENTER_GENERATORS[1]), [0, 2, 0, 0, 2, 0]);
+        assert_eq!(centers(CENTER_GENERATORS[2]), [1, 0, 0, 0, 3, 0]);
+        assert_eq!(centers(CENTER_GENERATORS[3]), [1, 0, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_center_fix() {
+        let mut count = 0;
+        for idx in 0..N_CENTERS {
+            let centers: [u8; 6] = std::array::from_fn(|f| (idx >> (2 * f)) as u8 % 4);
+            let cube = SuperCube {
+                centers,
+                ..Default::default()
+            };
+            match center_fix(&centers) {
+                Ok(fix) => {
+                    assert!(cube.apply_moves(&fix).is_solved());
+                    count += 1;
+                }
+                Err(_) => assert!(cube.verify().is_err()),
+            }
+        }
+        // all orientations with even sum
+        assert_eq!(count, 2048);
+        assert_eq!(center_fix(&[0, 0, 1, 0, 0, 0]).ok(), None);
+    }
+
+    #[test]
+    fn test_supercube() {
+        let cube = SuperCube::from(&vec![R, U, F3]);
+        assert_eq!(cube.centers, [1, 1, 3, 0, 0, 0]);
+        assert!(cube.verify().unwrap());
+        let s = cube.to_string();
+        assert!(s.ends_with("113000"));
+        assert_eq!(SuperCube::try_from(s.as_str()).unwrap(), cube);
+        assert!(SuperCube::try_from(&s[..54]).is_err());
+        let cube = SuperCube {
+            centers: [255, 255, 0, 0, 0, 0],
+            ..Default::default()
+        };
+        assert!(matches!(cube.verify(), Err(Error::InvalidCenterOrientation)));
+
+        // the cubies of U2 U2 F2 F2 are solved, the centers too
+        assert!(SuperCube::from(&vec![U2, U2, F2, F2]).is_solved());
+        let cube = SuperCube {
+            centers: [2, 0, 0, 0, 0, 0],
+            ..Default::default()
+        };
+        let limits = SearchLimits::default();
+        let solution = solve_supercube(&cube, &limits).unwrap().solution;
+        assert!(cube.apply_moves(&solution).is_solved());
+
+        let cube = SuperCube::from(&vec![R, U, R3, U3, F2, D, L2, B]);
+        let solution = solve_supercube(&cube, &limits).unwrap().solution;
+        assert!(cube.apply_moves(&solution).is_solved());
+    }
+}
@@ -2,7 +2,8 @@ use crate::constants::*;
 use crate::cubie::move_cubes;
 use crate::cubie::{Corner::*, CubieCube, Edge::*};
 use crate::error::Error;
-use crate::{decode_table, write_table};
+use crate::moves::Move;
+use crate::{create_tables_dir, decode_table, write_table};
 
 #[allow(non_camel_case_types)]
 enum BS {
@@ -151,6 +152,20 @@ pub fn inv_idx() -> [u8; 48] {
     inv_idx_arr
 }
 
+/// Give s^-1 * cc * s.
+pub(crate) fn conjugate(s: &CubieCube, cc: &CubieCube) -> CubieCube {
+    let mut d = s.inverse_cubie_cube();
+    d.multiply(*cc);
+    d.multiply(*s);
+    d
+}
+
+/// Give the move s^-1 * m * s for a rotation s.
+pub(crate) fn conjugate_move(s: &CubieCube, m: Move) -> Move {
+    let conj = conjugate(s, &move_cubes()[m as usize]);
+    ALL_MOVES[move_cubes().iter().position(|mc| *mc == conj).unwrap()] // rotations map moves to moves
+}
+
 /// Generate the group table for the 48 cube symmetries.
 fn mult_sym() -> Vec<usize> {
     let mut sym_mult = vec![0; N_SYM * N_SYM];
@@ -177,7 +192,7 @@ fn mult_sym() -> Vec<usize> {
 }
 
 /// Generate the table for the conjugation of a move m by a symmetry s. conj_move[N_MOVE*s + m] = s*m*s^-1
-fn conj_move() -> Vec<usize> {
+pub(crate) fn conj_move() -> Vec<usize> {
     let sc = sc();
     let mc = move_cubes();
     let inv_idx = inv_idx();
@@ -206,7 +221,7 @@ fn conj_move() -> Vec<usize> {
 fn conj_twist() -> Result<Vec<u16>, Error> {
     let sc = sc();
     let inv_idx = inv_idx();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/conj_twist";
     let conj_table = std::fs::read(&fname).unwrap_or("".into());
     let mut twist_conj = vec![0; N_TWIST * N_SYM_D4H];
@@ -243,7 +258,7 @@ fn conj_twist() -> Result<Vec<u16>, Error> {
 fn conj_ud_edges() -> Result<Vec<u16>, Error> {
     let sc = sc();
     let inv_idx = inv_idx();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname = "tables/conj_ud_edges";
     let conj_table = std::fs::read(&fname).unwrap_or("".into());
     let mut ud_edges_conj = vec![0; N_UD_EDGES * N_SYM_D4H];
@@ -290,7 +305,7 @@ pub struct FlipSliceSyms {
 pub fn flipslice_syms() -> Result<FlipSliceSyms, Error> {
     let sc = sc();
     let inv_idx = inv_idx();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname1 = "tables/fs_classidx";
     let fname2 = "tables/fs_sym";
     let fname3 = "tables/fs_rep";
@@ -372,7 +387,7 @@ pub struct CornerSyms {
 pub fn corner_syms() -> Result<CornerSyms, Error> {
     let sc = sc();
     let inv_idx = inv_idx();
-    std::fs::create_dir_all("tables")?;
+    create_tables_dir()?;
     let fname1 = "tables/co_classidx";
     let fname2 = "tables/co_sym";
     let fname3 = "tables/co_rep";
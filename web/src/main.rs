@@ -5,18 +5,25 @@ use gloo::timers::callback::Interval;
 use web_sys::HtmlTextAreaElement as InputElement;
 use yew::events::KeyboardEvent;
 use yew::{html, Component, Context, Html, TargetCast};
+#[cfg(not(feature = "local"))]
 use ehttp;
 
 use kociemba::cubie::CubieCube;
 use kociemba::facelet::FaceCube;
 use kociemba::moves::Move;
 use kociemba::scramble::scramble_from_str;
+#[cfg(feature = "local")]
+use kociemba::solver::solve_single_threaded;
 use kociemba::solver::SoutionResult;
 
 use cubie::{Color, Cubie, Face};
 
 mod cubie;
 
+/// Search budget of the solver in the browser, the result does not depend on the speed of the machine.
+#[cfg(feature = "local")]
+const MAX_NODES: u64 = 10_000_000;
+
 pub enum Msg {
     Random,
     Clean,
@@ -38,6 +45,20 @@ pub struct App {
 }
 
 impl App {
+    /// Solve the cube in the browser, the first solve creates the data tables.
+    #[cfg(feature = "local")]
+    pub fn solve(&mut self) {
+        match solve_single_threaded(&self.facelet, 20, MAX_NODES) {
+            Ok(solution) => {
+                log!(format!("Solution: {:?}", solution));
+                *self.solution_result.lock().unwrap() = solution;
+            }
+            Err(e) => log!(format!("No solution: {}", e)),
+        }
+    }
+
+    /// Solve the cube with kociemba-server.
+    #[cfg(not(feature = "local"))]
     pub fn solve(&mut self) {
         let host = "127.0.0.1";
         let port = 32125;